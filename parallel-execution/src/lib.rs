@@ -0,0 +1,387 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use pbc::generate::random_clifford_t;
+use pbc::spc_compact::spc_compact_translation;
+use pbc::{peephole_fuse_single_qubit, spc_translation};
+
+/// Computes the `n`-th Fibonacci number the slow, recursive way — useful as
+/// a CPU-bound workload for benchmarking thread parallelism.
+pub fn fib(n: u64) -> u64 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+/// The number of steps the Collatz sequence starting at `n` takes to reach
+/// 1 (`n` itself counts as 0 steps already at 1).
+pub fn collatz_steps(n: u64) -> u64 {
+    let mut n = n.max(1);
+    let mut steps = 0;
+    while n != 1 {
+        n = if n.is_multiple_of(2) { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+    steps
+}
+
+/// The number of primes less than or equal to `n`, by trial division --
+/// deliberately the naive approach, since this exists as a CPU-bound
+/// benchmark workload rather than a production primality test.
+pub fn count_primes_up_to(n: u64) -> u64 {
+    (2..=n).filter(|&candidate| is_prime(candidate)).count() as u64
+}
+
+fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut divisor = 2;
+    while divisor * divisor <= n {
+        if n.is_multiple_of(divisor) {
+            return false;
+        }
+        divisor += 1;
+    }
+    true
+}
+
+/// `num_qubits`/`t_fraction`/`seed` for the synthetic circuit `Workload::Spc`
+/// translates: fixed so the workload is deterministic and comparable across
+/// runs, with `n` (the benchmark's usual "problem size" knob) driving depth.
+const SPC_NUM_QUBITS: usize = 4;
+const SPC_T_FRACTION: f64 = 0.3;
+const SPC_SEED: u64 = 42;
+
+/// Runs the spc-translation pipeline (peephole fusion, frame-absorbing
+/// translation, then compact diagonalization -- the same stages
+/// `spc`'s CLI exposes as `translate`/`compact`) over a deterministic
+/// synthetic Clifford+T circuit of `n` gates, returning the final operator
+/// count. Checks `cancel` between phases and bails out to 0 early if it's
+/// set, since a phase that walks the whole operator sequence is pointless
+/// to start once the caller has stopped waiting on the result.
+fn run_spc_translation(n: u64, cancel: &AtomicBool) -> u64 {
+    let circuit = random_clifford_t(SPC_NUM_QUBITS, n as usize, SPC_T_FRACTION, SPC_SEED);
+    if cancel.load(Ordering::Relaxed) {
+        return 0;
+    }
+
+    let fused = peephole_fuse_single_qubit(&circuit);
+    if cancel.load(Ordering::Relaxed) {
+        return 0;
+    }
+
+    let translated = spc_translation(&fused);
+    if cancel.load(Ordering::Relaxed) {
+        return 0;
+    }
+
+    spc_compact_translation(&translated).len() as u64
+}
+
+/// The per-task compute kernel a benchmark run exercises, selected by the
+/// `--workload` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workload {
+    Fib,
+    Collatz,
+    Prime,
+    Spc,
+}
+
+impl Workload {
+    /// Runs this workload's kernel on `n`. `cancel` is only consulted by
+    /// `Workload::Spc`, between pipeline phases -- the other workloads have
+    /// no natural phase boundary to check it at, so they ignore it and
+    /// always run to completion.
+    pub fn run(&self, n: u64, cancel: &AtomicBool) -> u64 {
+        match self {
+            Workload::Fib => fib(n),
+            Workload::Collatz => collatz_steps(n),
+            Workload::Prime => count_primes_up_to(n),
+            Workload::Spc => run_spc_translation(n, cancel),
+        }
+    }
+}
+
+impl std::str::FromStr for Workload {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Workload, String> {
+        match s {
+            "fib" => Ok(Workload::Fib),
+            "collatz" => Ok(Workload::Collatz),
+            "prime" => Ok(Workload::Prime),
+            "spc" => Ok(Workload::Spc),
+            other => Err(format!("unknown workload '{}': expected fib, collatz, prime, or spc", other)),
+        }
+    }
+}
+
+/// The outcome of running a single task.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskOutcome {
+    Completed { duration: Duration, result: u64 },
+    TimedOut,
+}
+
+/// Runs `parallelism` copies of `workload.run(n)`, one per thread, and
+/// waits at most `timeout` for each to report back. Rust can't forcibly
+/// kill a thread, so a timed-out task's worker thread is simply left to
+/// run in the background while the caller moves on -- all copies share one
+/// `cancel` flag, though, so as soon as any of them times out, the rest
+/// get a chance to notice and stop early at their next phase boundary
+/// instead of running all the way to completion for nothing (only
+/// `Workload::Spc` actually checks it; the others run to completion
+/// regardless). A `None` timeout waits forever.
+pub fn run_tasks(workload: Workload, n: u64, parallelism: usize, timeout: Option<Duration>) -> Vec<TaskOutcome> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let receivers: Vec<_> = (0..parallelism)
+        .map(|_| {
+            let (tx, rx) = mpsc::channel();
+            let cancel = Arc::clone(&cancel);
+            thread::spawn(move || {
+                let start = Instant::now();
+                let result = workload.run(n, &cancel);
+                let _ = tx.send((start.elapsed(), result));
+            });
+            rx
+        })
+        .collect();
+
+    receivers
+        .into_iter()
+        .map(|rx| {
+            let received = match timeout {
+                Some(timeout) => rx.recv_timeout(timeout).ok(),
+                None => rx.recv().ok(),
+            };
+            match received {
+                Some((duration, result)) => TaskOutcome::Completed { duration, result },
+                None => {
+                    cancel.store(true, Ordering::Relaxed);
+                    TaskOutcome::TimedOut
+                }
+            }
+        })
+        .collect()
+}
+
+/// Whether any task in `outcomes` timed out.
+pub fn any_timed_out(outcomes: &[TaskOutcome]) -> bool {
+    outcomes.iter().any(|o| matches!(o, TaskOutcome::TimedOut))
+}
+
+/// Parses a comma-separated list of values, e.g. `"28,30,32,34"`.
+pub fn parse_list<T: std::str::FromStr>(s: &str) -> Result<Vec<T>, String>
+where
+    T::Err: std::fmt::Display,
+{
+    s.split(',')
+        .map(|part| {
+            part.trim()
+                .parse::<T>()
+                .map_err(|e| format!("invalid value '{}': {}", part.trim(), e))
+        })
+        .collect()
+}
+
+/// The cartesian product of `ns` and `parallelisms`, in `n`-major order: all
+/// parallelism values for the first `n`, then the second, and so on.
+pub fn cartesian_product(ns: &[u64], parallelisms: &[usize]) -> Vec<(u64, usize)> {
+    ns.iter()
+        .flat_map(|&n| parallelisms.iter().map(move |&p| (n, p)))
+        .collect()
+}
+
+/// The mean duration of the completed tasks in `outcomes`, or `None` if none
+/// completed.
+pub fn mean_duration(outcomes: &[TaskOutcome]) -> Option<Duration> {
+    let completed: Vec<Duration> = outcomes
+        .iter()
+        .filter_map(|o| match o {
+            TaskOutcome::Completed { duration, .. } => Some(*duration),
+            TaskOutcome::TimedOut => None,
+        })
+        .collect();
+    if completed.is_empty() {
+        return None;
+    }
+    Some(completed.iter().sum::<Duration>() / completed.len() as u32)
+}
+
+/// The speedup of `duration` relative to a `baseline` (parallelism 1)
+/// duration.
+pub fn speedup(duration: Duration, baseline: Duration) -> f64 {
+    baseline.as_secs_f64() / duration.as_secs_f64()
+}
+
+/// One row of a parameter sweep: a given `(n, parallelism)` pair, its mean
+/// task duration, and its speedup relative to the parallelism-1 result for
+/// the same `n` (`None` if parallelism 1 wasn't part of the sweep, or if it
+/// timed out).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepRow {
+    pub n: u64,
+    pub parallelism: usize,
+    pub mean_duration: Option<Duration>,
+    pub speedup: Option<f64>,
+}
+
+/// Runs the full cartesian product of `ns` x `parallelisms`, producing one
+/// [`SweepRow`] per pair. Each row's speedup is relative to the
+/// parallelism-1 row for the same `n`, if one was run.
+pub fn run_sweep(workload: Workload, ns: &[u64], parallelisms: &[usize], timeout: Option<Duration>) -> Vec<SweepRow> {
+    let mut rows: Vec<SweepRow> = cartesian_product(ns, parallelisms)
+        .into_iter()
+        .map(|(n, parallelism)| {
+            let outcomes = run_tasks(workload, n, parallelism, timeout);
+            SweepRow {
+                n,
+                parallelism,
+                mean_duration: mean_duration(&outcomes),
+                speedup: None,
+            }
+        })
+        .collect();
+
+    for n in ns {
+        let baseline = rows
+            .iter()
+            .find(|row| row.n == *n && row.parallelism == 1)
+            .and_then(|row| row.mean_duration);
+        if let Some(baseline) = baseline {
+            for row in rows.iter_mut().filter(|row| row.n == *n) {
+                row.speedup = row.mean_duration.map(|duration| speedup(duration, baseline));
+            }
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fib() {
+        assert_eq!(fib(0), 0);
+        assert_eq!(fib(1), 1);
+        assert_eq!(fib(10), 55);
+    }
+
+    #[test]
+    fn test_run_tasks_without_timeout() {
+        let outcomes = run_tasks(Workload::Fib, 10, 4, None);
+        assert_eq!(outcomes.len(), 4);
+        assert!(!any_timed_out(&outcomes));
+        for outcome in outcomes {
+            match outcome {
+                TaskOutcome::Completed { result, .. } => assert_eq!(result, 55),
+                TaskOutcome::TimedOut => panic!("unexpected timeout"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_tasks_with_timeout() {
+        // A slow task (large n) with a very short timeout should be
+        // reported as timed out, and the exit-code helper should notice.
+        let outcomes = run_tasks(Workload::Fib, 35, 1, Some(Duration::from_nanos(1)));
+        assert_eq!(outcomes, vec![TaskOutcome::TimedOut]);
+        assert!(any_timed_out(&outcomes));
+    }
+
+    #[test]
+    fn test_workload_from_str() {
+        assert_eq!("fib".parse::<Workload>(), Ok(Workload::Fib));
+        assert_eq!("collatz".parse::<Workload>(), Ok(Workload::Collatz));
+        assert_eq!("prime".parse::<Workload>(), Ok(Workload::Prime));
+        assert_eq!("spc".parse::<Workload>(), Ok(Workload::Spc));
+        assert!("banana".parse::<Workload>().is_err());
+    }
+
+    #[test]
+    fn test_collatz_steps() {
+        assert_eq!(collatz_steps(1), 0);
+        assert_eq!(collatz_steps(6), 8);
+    }
+
+    #[test]
+    fn test_count_primes_up_to() {
+        assert_eq!(count_primes_up_to(1), 0);
+        assert_eq!(count_primes_up_to(10), 4);
+    }
+
+    #[test]
+    fn test_each_workload_is_deterministic_for_a_fixed_n() {
+        for workload in [Workload::Fib, Workload::Collatz, Workload::Prime, Workload::Spc] {
+            let first = workload.run(20, &AtomicBool::new(false));
+            let second = workload.run(20, &AtomicBool::new(false));
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_spc_workload_translates_a_nonempty_circuit() {
+        let count = Workload::Spc.run(20, &AtomicBool::new(false));
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn test_spc_workload_bails_out_early_once_cancelled() {
+        assert_eq!(Workload::Spc.run(20, &AtomicBool::new(true)), 0);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list::<u64>("28,30,32,34").unwrap(), vec![28, 30, 32, 34]);
+        assert_eq!(parse_list::<usize>(" 1, 2 ,4 ").unwrap(), vec![1, 2, 4]);
+        assert!(parse_list::<u64>("28,banana").is_err());
+    }
+
+    #[test]
+    fn test_cartesian_product() {
+        assert_eq!(
+            cartesian_product(&[28, 30], &[1, 2]),
+            vec![(28, 1), (28, 2), (30, 1), (30, 2)]
+        );
+    }
+
+    #[test]
+    fn test_mean_duration() {
+        let outcomes = vec![
+            TaskOutcome::Completed { duration: Duration::from_secs(1), result: 55 },
+            TaskOutcome::Completed { duration: Duration::from_secs(3), result: 55 },
+        ];
+        assert_eq!(mean_duration(&outcomes), Some(Duration::from_secs(2)));
+        assert_eq!(mean_duration(&[TaskOutcome::TimedOut]), None);
+    }
+
+    #[test]
+    fn test_speedup() {
+        assert_eq!(speedup(Duration::from_secs(1), Duration::from_secs(4)), 4.0);
+        assert_eq!(speedup(Duration::from_secs(2), Duration::from_secs(2)), 1.0);
+    }
+
+    #[test]
+    fn test_run_sweep_computes_speedup_relative_to_parallelism_one() {
+        let rows = run_sweep(Workload::Fib, &[10], &[1, 2], None);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].parallelism, 1);
+        assert_eq!(rows[0].speedup, Some(1.0));
+        assert_eq!(rows[1].parallelism, 2);
+        assert!(rows[1].speedup.is_some());
+    }
+
+    #[test]
+    fn test_run_sweep_without_parallelism_one_has_no_speedup() {
+        let rows = run_sweep(Workload::Fib, &[10], &[2, 4], None);
+        assert!(rows.iter().all(|row| row.speedup.is_none()));
+    }
+}
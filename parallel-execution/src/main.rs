@@ -0,0 +1,96 @@
+use std::process::ExitCode;
+use std::time::Duration;
+
+use parallel_execution::{parse_list, run_sweep, SweepRow, Workload};
+
+struct Args {
+    ns: Vec<u64>,
+    parallelisms: Vec<usize>,
+    timeout: Option<Duration>,
+    csv: bool,
+    workload: Workload,
+}
+
+fn parse_args() -> Args {
+    let mut ns = vec![30u64];
+    let mut parallelisms = vec![4usize];
+    let mut timeout = None;
+    let mut csv = false;
+    let mut workload = Workload::Fib;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" => {
+                let value = args.next().expect("-n requires a value");
+                ns = parse_list(&value).expect("-n must be a comma-separated list of integers");
+            }
+            "--parallelism" => {
+                let value = args.next().expect("--parallelism requires a value");
+                parallelisms = parse_list(&value)
+                    .expect("--parallelism must be a comma-separated list of integers");
+            }
+            "--timeout" => {
+                let secs: f64 = args
+                    .next()
+                    .expect("--timeout requires a value")
+                    .parse()
+                    .expect("--timeout must be a number of seconds");
+                timeout = Some(Duration::from_secs_f64(secs));
+            }
+            "--workload" => {
+                let value = args.next().expect("--workload requires a value");
+                workload = value.parse().expect("--workload must be fib, collatz, prime, or spc");
+            }
+            "--csv" => csv = true,
+            other => panic!("unrecognized argument: {}", other),
+        }
+    }
+
+    Args { ns, parallelisms, timeout, csv, workload }
+}
+
+fn format_cell(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".to_string())
+}
+
+fn print_table(rows: &[SweepRow]) {
+    println!(
+        "{:>6} {:>12} {:>16} {:>10}",
+        "n", "parallelism", "mean_duration", "speedup"
+    );
+    for row in rows {
+        let mean_duration = format_cell(row.mean_duration.map(|d| format!("{:?}", d)));
+        let speedup = format_cell(row.speedup.map(|s| format!("{:.2}x", s)));
+        println!(
+            "{:>6} {:>12} {:>16} {:>10}",
+            row.n, row.parallelism, mean_duration, speedup
+        );
+    }
+}
+
+fn print_csv(rows: &[SweepRow]) {
+    println!("n,parallelism,mean_duration_secs,speedup");
+    for row in rows {
+        let mean_duration = format_cell(row.mean_duration.map(|d| format!("{}", d.as_secs_f64())));
+        let speedup = format_cell(row.speedup.map(|s| format!("{}", s)));
+        println!("{},{},{},{}", row.n, row.parallelism, mean_duration, speedup);
+    }
+}
+
+fn main() -> ExitCode {
+    let args = parse_args();
+    let rows = run_sweep(args.workload, &args.ns, &args.parallelisms, args.timeout);
+
+    if args.csv {
+        print_csv(&rows);
+    } else {
+        print_table(&rows);
+    }
+
+    if rows.iter().any(|row| row.mean_duration.is_none()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,59 @@
+//! Benchmarks showing the cost of repeatedly computing `is_clifford` and
+//! weight/support on the same circuit, versus reading them once from
+//! `AnnotatedOperator`, using a reproducible (fixed-seed) random circuit
+//! from `pbc::test_support`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use pbc::test_support::random_circuit;
+use pbc::{annotate_operators, Operator, Pauli};
+
+const WIDTHS: [usize; 4] = [16, 64, 256, 1024];
+const SEED: u64 = 0x5eed;
+
+fn weight(op: &Operator) -> usize {
+    match op.axis() {
+        Some(axis) => axis.as_slice().iter().filter(|&&p| p != Pauli::I).count(),
+        None => 1,
+    }
+}
+
+fn bench_recompute_each_pass(c: &mut Criterion) {
+    let mut group = c.benchmark_group("recompute_is_clifford_and_weight");
+    for &width in &WIDTHS {
+        let circuit = random_circuit(SEED, width, 256);
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |bencher, _| {
+            bencher.iter(|| {
+                // A stand-in for several downstream passes each asking the
+                // same questions about every operator.
+                for _ in 0..4 {
+                    for op in &circuit {
+                        std::hint::black_box((op.is_clifford(), weight(op)));
+                    }
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_read_cached_annotations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read_cached_is_clifford_and_weight");
+    for &width in &WIDTHS {
+        let circuit = random_circuit(SEED, width, 256);
+        let annotated = annotate_operators(&circuit);
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |bencher, _| {
+            bencher.iter(|| {
+                for _ in 0..4 {
+                    for op in &annotated {
+                        std::hint::black_box((op.is_clifford, op.weight));
+                    }
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_recompute_each_pass, bench_read_cached_annotations);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+//! Benchmarks for `Axis`-level operations across circuit sizes, using
+//! reproducible (fixed-seed) random circuits from `pbc::test_support`.
+//!
+//! NOTE: this only covers the current `Vec<Pauli>`-backed `Axis`. The
+//! bit-packed/sparse representations this benchmark suite was written to
+//! eventually compare don't exist in this crate yet; once they land, add
+//! their `commutes_with`/`transform`/`spc_translation` variants alongside
+//! the ones benchmarked here.
+
+use std::rc::Rc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use pbc::spc::{spc_translation, transform, transform_single_pass};
+use pbc::test_support::{random_axis, random_circuit, Rng};
+
+const WIDTHS: [usize; 4] = [16, 64, 256, 1024];
+const SEED: u64 = 0x5eed;
+
+fn bench_commutes_with(c: &mut Criterion) {
+    let mut group = c.benchmark_group("commutes_with");
+    for &width in &WIDTHS {
+        let mut rng = Rng::new(SEED);
+        let a = random_axis(&mut rng, width);
+        let b = random_axis(&mut rng, width);
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |bencher, _| {
+            bencher.iter(|| a.commutes_with(&b));
+        });
+    }
+    group.finish();
+}
+
+fn bench_transform(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transform");
+    for &width in &WIDTHS {
+        let circuit = random_circuit(SEED, width, 8);
+        let frame_op = match &circuit[0] {
+            pbc::Operator::PauliRotation(rotation) => rotation.clone(),
+            _ => unreachable!(),
+        };
+        let mut rng = Rng::new(SEED + 1);
+        let axis = Rc::new(random_axis(&mut rng, width));
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |bencher, _| {
+            bencher.iter(|| transform(Rc::clone(&axis), &frame_op));
+        });
+    }
+    group.finish();
+}
+
+/// Compares `transform`'s two-pass commutation check against
+/// `transform_single_pass`'s fused single pass, on the same random axes
+/// `bench_transform` uses.
+fn bench_transform_single_pass(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transform_single_pass");
+    for &width in &WIDTHS {
+        let circuit = random_circuit(SEED, width, 8);
+        let frame_op = match &circuit[0] {
+            pbc::Operator::PauliRotation(rotation) => rotation.clone(),
+            _ => unreachable!(),
+        };
+        let mut rng = Rng::new(SEED + 1);
+        let axis = Rc::new(random_axis(&mut rng, width));
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |bencher, _| {
+            bencher.iter(|| transform_single_pass(Rc::clone(&axis), &frame_op));
+        });
+    }
+    group.finish();
+}
+
+fn bench_spc_translation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spc_translation");
+    for &width in &WIDTHS {
+        let circuit = random_circuit(SEED, width, 64);
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |bencher, _| {
+            bencher.iter(|| spc_translation(&circuit));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_commutes_with,
+    bench_transform,
+    bench_transform_single_pass,
+    bench_spc_translation
+);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+//! A global allocator that counts allocations, for tests that want to
+//! demonstrate (rather than just assert) that a hot path avoids allocating.
+//! Test-only: a binary can only have one `#[global_allocator]`, so this
+//! module is compiled exclusively under `#[cfg(test)]`.
+
+#![cfg(test)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// The number of allocations made so far, for measuring the allocations
+/// made by a specific call (take the count before and after and diff them).
+pub(crate) fn allocation_count() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
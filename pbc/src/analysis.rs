@@ -0,0 +1,517 @@
+use crate::axis::Axis;
+use crate::operator::Operator;
+use crate::pauli::Pauli;
+
+/// The number of qubits where both `a` and `b` act non-trivially (neither
+/// is `Pauli::I`), regardless of whether their Paulis agree there. A
+/// building block for scheduling checks that only care whether two axes'
+/// supports intersect at all -- note this counts *any* dual non-identity
+/// overlap, including qubits where `a` and `b` use the same Pauli (which
+/// commutes) alongside ones where they differ (which doesn't); see
+/// `anticommuting_overlap_count` for the commutation-aware count.
+pub fn overlapping_support_count(a: &Axis, b: &Axis) -> usize {
+    assert_eq!(a.width(), b.width());
+    a.as_slice()
+        .iter()
+        .zip(b.as_slice().iter())
+        .filter(|(x, y)| **x != Pauli::I && **y != Pauli::I)
+        .count()
+}
+
+/// The number of qubits where `a` and `b` act non-trivially *and*
+/// anticommute there (different non-identity Paulis). Unlike
+/// `overlapping_support_count`, a qubit where both axes use the *same*
+/// Pauli (e.g. both `Z`) isn't counted here, since that overlap commutes
+/// and doesn't carry the scheduling restriction genuine anticommuting
+/// overlap does.
+pub fn anticommuting_overlap_count(a: &Axis, b: &Axis) -> usize {
+    assert_eq!(a.width(), b.width());
+    a.as_slice()
+        .iter()
+        .zip(b.as_slice().iter())
+        .filter(|(x, y)| **x != Pauli::I && **y != Pauli::I && x != y)
+        .count()
+}
+
+/// The length of the longest run of consecutive operators in `ops` where
+/// each one anticommutes with the one before it: a proxy for how much of
+/// the circuit is unavoidably serialized, since adjacent anticommuting
+/// operators can't be reordered or applied in parallel. Computed via a
+/// simple DP: extend the current run by one when the adjacent pair
+/// anticommutes, otherwise restart it at length 1. A `Reset` has no axis to
+/// compare, so it never anticommutes with its neighbor and always breaks
+/// the run.
+pub fn longest_anticommuting_chain(ops: &[Operator]) -> usize {
+    if ops.is_empty() {
+        return 0;
+    }
+
+    let mut longest = 1;
+    let mut current = 1;
+    for i in 1..ops.len() {
+        let anticommutes = match (ops[i - 1].axis(), ops[i].axis()) {
+            (Some(a), Some(b)) => !a.commutes_with(b),
+            _ => false,
+        };
+        current = if anticommutes { current + 1 } else { 1 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// The pairwise commutation matrix for `ops`: entry `[i][j]` is whether
+/// `ops[i]` and `ops[j]` can be swapped (see `Operator::can_swap_with`).
+/// Symmetric, with every diagonal entry `true` (an operator always commutes
+/// with itself) except for a `Barrier` or `Conditional`, which
+/// `can_swap_with` never lets move, not even past itself. Used as a strong correctness check on `spc_translation`:
+/// conjugating by a Clifford frame preserves commutation, so the matrix
+/// restricted to the non-Clifford operators should be identical before and
+/// after translation.
+pub fn commutation_matrix(ops: &[Operator]) -> Vec<Vec<bool>> {
+    ops.iter().map(|a| ops.iter().map(|b| a.can_swap_with(b)).collect()).collect()
+}
+
+/// Returns index pairs `(i, j)` (with `i < j`) of measurements in `ops`
+/// whose axes anticommute. Such pairs can't both be deterministic, and are
+/// a sign that the circuit's output carries genuine quantum randomness.
+pub fn anticommuting_measurement_pairs(ops: &[Operator]) -> Vec<(usize, usize)> {
+    let measurements: Vec<(usize, &Axis)> = ops
+        .iter()
+        .enumerate()
+        .filter_map(|(i, op)| match op {
+            Operator::Measurement { axis, .. } => Some((i, axis)),
+            _ => None,
+        })
+        .collect();
+
+    let mut pairs = Vec::new();
+    for a in 0..measurements.len() {
+        for b in (a + 1)..measurements.len() {
+            let (i, axis_i) = measurements[a];
+            let (j, axis_j) = measurements[b];
+            if !axis_i.commutes_with(axis_j) {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+/// The net Pauli correction each measurement in `ops` carries, as
+/// `(index, corrected_axis)` pairs -- the real-time decoding "Pauli
+/// frame" a later consumer of a measurement's outcome would need to
+/// account for. `ops` is expected to already be `spc_translation`'s
+/// output: a measurement's axis there has already been conjugated
+/// through every Clifford absorbed ahead of it, so the axis reported
+/// here is exactly that correction, not the measurement's original,
+/// uncorrected axis.
+pub fn phase_frame(ops: &[Operator]) -> Vec<(usize, Axis)> {
+    ops.iter()
+        .enumerate()
+        .filter_map(|(i, op)| match op {
+            Operator::Measurement { axis, .. } => Some((i, axis.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Indices of `Measurement`s in `ops` whose axis is all-identity. Such a
+/// measurement carries no information -- it measures nothing -- and is
+/// almost always a bug, whether it came in that way from bad input or
+/// degenerated to identity after conjugation through a Clifford frame.
+pub fn identity_measurements(ops: &[Operator]) -> Vec<usize> {
+    ops.iter()
+        .enumerate()
+        .filter_map(|(i, op)| match op {
+            Operator::Measurement { axis, .. } if axis.as_slice().iter().all(|p| *p == Pauli::I) => Some(i),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The distinct axes, in order of first appearance, that non-Clifford
+/// rotations and measurements in `ops` act on -- the magic-state axes a
+/// factory-scheduling tool would need to plan for. A measurement's axis is
+/// always included even though `Operator::is_clifford` reports `true` for
+/// measurements: that flag is about whether the operator can be absorbed
+/// into/commuted through a Clifford frame, not about factory cost, and
+/// every measurement still needs its own readout resource. `Reset` and
+/// `Barrier` never contribute, since neither has an axis. A `Conditional`
+/// defers to whatever it wraps.
+pub fn magic_state_axes(ops: &[Operator]) -> Vec<Axis> {
+    fn is_magic(op: &Operator) -> bool {
+        match op {
+            Operator::PauliRotation(r) => !r.is_clifford(),
+            Operator::Measurement { .. } => true,
+            Operator::Reset { .. } | Operator::Barrier(_) => false,
+            Operator::Conditional { inner, .. } => is_magic(inner),
+        }
+    }
+
+    let mut axes: Vec<Axis> = Vec::new();
+    for op in ops {
+        if is_magic(op) {
+            let axis = op.axis().expect("rotations and measurements have axes").clone();
+            if !axes.contains(&axis) {
+                axes.push(axis);
+            }
+        }
+    }
+    axes
+}
+
+/// T-count attributed to each qubit: for every non-Clifford (magic-state)
+/// rotation in `ops`, one T-count is added to every qubit in its axis's
+/// support, since executing it costs a magic state on each qubit it
+/// touches. Clifford rotations, measurements, and resets never contribute.
+/// The result has one entry per qubit, sized to the widest axis seen in
+/// `ops` (empty if `ops` is empty or touches no qubits).
+pub fn t_count_per_qubit(ops: &[Operator]) -> Vec<usize> {
+    let width = ops
+        .iter()
+        .filter_map(Operator::axis)
+        .map(|axis| axis.as_slice().len())
+        .max()
+        .unwrap_or(0);
+    let mut counts = vec![0; width];
+    for op in ops {
+        if let Operator::PauliRotation(rotation) = op {
+            if !rotation.is_clifford() {
+                for (qubit, pauli) in rotation.axis.as_slice().iter().enumerate() {
+                    if *pauli != Pauli::I {
+                        counts[qubit] += 1;
+                    }
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// The number of measurement layers in `ops`: the minimum number of rounds
+/// a greedy packing needs to apply every measurement, where a round can
+/// include any number of pairwise-commuting measurements (since those can
+/// be measured simultaneously) but never two that anticommute. Non-
+/// measurement operators are ignored for the purposes of this metric.
+pub fn measurement_layers(ops: &[Operator]) -> usize {
+    let mut layers: Vec<Vec<&Axis>> = Vec::new();
+    for op in ops {
+        if let Operator::Measurement { axis, .. } = op {
+            let slot = layers
+                .iter()
+                .position(|layer| layer.iter().all(|existing| existing.commutes_with(axis)));
+            match slot {
+                Some(i) => layers[i].push(axis),
+                None => layers.push(vec![axis]),
+            }
+        }
+    }
+    layers.len()
+}
+
+/// The peak number of non-Clifford rotations that could execute in the
+/// same commutation layer: the size of the largest layer a greedy packing
+/// produces, where a layer can hold any number of pairwise-commuting
+/// non-Clifford rotations but never two that anticommute (the same
+/// packing rule `measurement_layers` uses for measurements). This bounds
+/// the number of parallel magic-state factories a circuit needs -- each
+/// rotation live in a layer consumes one. Clifford rotations,
+/// measurements, and resets never contribute.
+///
+/// This crate has no dedicated `commuting_groups` helper to build on, so
+/// this packs layers directly the same way `measurement_layers` does.
+pub fn peak_magic_parallelism(ops: &[Operator]) -> usize {
+    let mut layers: Vec<Vec<&Axis>> = Vec::new();
+    for op in ops {
+        if let Operator::PauliRotation(rotation) = op {
+            if !rotation.is_clifford() {
+                let axis = &rotation.axis;
+                let slot = layers
+                    .iter()
+                    .position(|layer| layer.iter().all(|existing| existing.commutes_with(axis)));
+                match slot {
+                    Some(i) => layers[i].push(axis),
+                    None => layers.push(vec![axis]),
+                }
+            }
+        }
+    }
+    layers.iter().map(Vec::len).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::Axis;
+    use crate::pauli::Pauli;
+
+    #[test]
+    fn test_anticommuting_measurement_pairs() {
+        let ops = vec![
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(1, 0, Pauli::X),
+                target: 0,
+            },
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(1, 0, Pauli::Z),
+                target: 1,
+            },
+        ];
+        assert_eq!(anticommuting_measurement_pairs(&ops), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_no_anticommuting_pairs() {
+        let ops = vec![
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(1, 0, Pauli::Z),
+                target: 0,
+            },
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(1, 0, Pauli::Z),
+                target: 1,
+            },
+        ];
+        assert_eq!(anticommuting_measurement_pairs(&ops), vec![]);
+    }
+
+    #[test]
+    fn test_overlapping_support_count_includes_commuting_overlap() {
+        // "ZZ" vs "ZI": one shared qubit, both Z -- commutes, but the
+        // supports still overlap there.
+        let a = Axis::new(vec![Pauli::Z, Pauli::Z]);
+        let b = Axis::new(vec![Pauli::Z, Pauli::I]);
+        assert_eq!(overlapping_support_count(&a, &b), 1);
+        assert_eq!(anticommuting_overlap_count(&a, &b), 0);
+    }
+
+    #[test]
+    fn test_anticommuting_overlap_count_only_counts_differing_paulis() {
+        // "XZ" vs "ZZ": qubit 0 anticommutes (X vs Z), qubit 1 commutes
+        // (Z vs Z) despite also overlapping.
+        let a = Axis::new(vec![Pauli::X, Pauli::Z]);
+        let b = Axis::new(vec![Pauli::Z, Pauli::Z]);
+        assert_eq!(overlapping_support_count(&a, &b), 2);
+        assert_eq!(anticommuting_overlap_count(&a, &b), 1);
+    }
+
+    #[test]
+    fn test_commutation_matrix_is_symmetric_with_a_true_diagonal() {
+        let ops = vec![
+            Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::Z), target: 0 },
+            Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::Z), target: 1 },
+        ];
+        let matrix = commutation_matrix(&ops);
+        assert_eq!(matrix, vec![vec![true, true], vec![true, true]]);
+    }
+
+    #[test]
+    fn test_commutation_matrix_flags_an_anticommuting_pair() {
+        let ops = vec![
+            Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::X), target: 0 },
+            Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::Z), target: 0 },
+        ];
+        let matrix = commutation_matrix(&ops);
+        assert_eq!(matrix, vec![vec![true, false], vec![false, true]]);
+    }
+
+    #[test]
+    fn test_phase_frame_reports_the_corrected_axis_for_a_measurement_after_a_clifford_frame() {
+        use crate::angle::Angle;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+        use crate::spc::spc_translation;
+
+        // H (as its Z(pi/4) X(pi/4) Z(pi/4) decomposition) followed by a
+        // measurement of X: H X H = Z, so the measurement's sign-dependent
+        // outcome is really reporting on Z once the frame is accounted
+        // for, not the X it was written against.
+        let h = |pauli| {
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, pauli),
+                Angle::PiOver8(Mod8::Two),
+            ))
+        };
+        let ops = vec![
+            h(Pauli::Z),
+            h(Pauli::X),
+            h(Pauli::Z),
+            Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::X), target: 0 },
+        ];
+
+        let translated = spc_translation(&ops);
+        assert_eq!(phase_frame(&translated), vec![(0, Axis::new_with_pauli(1, 0, Pauli::Z))]);
+    }
+
+    #[test]
+    fn test_identity_measurements_flags_an_all_identity_axis() {
+        let ops = vec![
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(4, 0, Pauli::X),
+                target: 0,
+            },
+            Operator::Measurement {
+                axis: Axis::identity(4),
+                target: 1,
+            },
+        ];
+        assert_eq!(identity_measurements(&ops), vec![1]);
+    }
+
+    #[test]
+    fn test_identity_measurements_ignores_non_measurement_operators_and_real_measurements() {
+        let ops = vec![measurement(Pauli::Z), measurement(Pauli::X)];
+        assert_eq!(identity_measurements(&ops), vec![]);
+    }
+
+    fn measurement(pauli: Pauli) -> Operator {
+        Operator::Measurement {
+            axis: Axis::new_with_pauli(1, 0, pauli),
+            target: 0,
+        }
+    }
+
+    #[test]
+    fn test_magic_state_axes_collects_distinct_axes_in_order() {
+        use crate::angle::Angle;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+
+        let t_on_zero = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        ));
+        let clifford_on_one = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 1, Pauli::X),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        let measure_one = measurement(Pauli::X);
+        let ops = vec![
+            t_on_zero.clone(),
+            clifford_on_one,
+            Operator::Reset { qubit: 0 },
+            t_on_zero.clone(),
+            measure_one.clone(),
+        ];
+
+        assert_eq!(
+            magic_state_axes(&ops),
+            vec![t_on_zero.axis().unwrap().clone(), measure_one.axis().unwrap().clone()]
+        );
+    }
+
+    #[test]
+    fn test_longest_anticommuting_chain() {
+        // X-Z and Z-X each anticommute (chain length 3), then X-I commute,
+        // breaking the chain before it can extend further.
+        let ops = vec![
+            measurement(Pauli::X),
+            measurement(Pauli::Z),
+            measurement(Pauli::X),
+            measurement(Pauli::I),
+        ];
+        assert_eq!(longest_anticommuting_chain(&ops), 3);
+    }
+
+    #[test]
+    fn test_longest_anticommuting_chain_empty() {
+        assert_eq!(longest_anticommuting_chain(&[]), 0);
+    }
+
+    #[test]
+    fn test_longest_anticommuting_chain_all_commuting() {
+        let ops = vec![measurement(Pauli::Z), measurement(Pauli::Z)];
+        assert_eq!(longest_anticommuting_chain(&ops), 1);
+    }
+
+    #[test]
+    fn test_t_count_per_qubit_counts_a_multi_qubit_rotation_toward_each_support_qubit() {
+        use crate::angle::Angle;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+
+        // A single non-Clifford ZZ rotation on qubits 0 and 1 contributes 1
+        // T-count to each.
+        let ops = vec![Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_paulis(2, &[(0, Pauli::Z), (1, Pauli::Z)]),
+            Angle::PiOver8(Mod8::One),
+        ))];
+        assert_eq!(t_count_per_qubit(&ops), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_t_count_per_qubit_ignores_clifford_rotations_and_measurements() {
+        use crate::angle::Angle;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            measurement(Pauli::Z),
+        ];
+        assert_eq!(t_count_per_qubit(&ops), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_measurement_layers_packs_commuting_measurements_into_one_layer() {
+        let ops = vec![measurement(Pauli::Z), measurement(Pauli::Z)];
+        assert_eq!(measurement_layers(&ops), 1);
+    }
+
+    #[test]
+    fn test_measurement_layers_needs_a_separate_layer_for_anticommuting_measurements() {
+        let ops = vec![measurement(Pauli::X), measurement(Pauli::Z)];
+        assert_eq!(measurement_layers(&ops), 2);
+    }
+
+    #[test]
+    fn test_measurement_layers_ignores_non_measurement_operators() {
+        let ops = vec![Operator::Reset { qubit: 0 }, measurement(Pauli::Z)];
+        assert_eq!(measurement_layers(&ops), 1);
+    }
+
+    #[test]
+    fn test_peak_magic_parallelism_packs_three_commuting_non_clifford_rotations_into_one_layer() {
+        use crate::angle::Angle;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+
+        let t_on = |qubit| {
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(3, qubit, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            ))
+        };
+        let ops = vec![t_on(0), t_on(1), t_on(2)];
+
+        assert_eq!(peak_magic_parallelism(&ops), 3);
+    }
+
+    #[test]
+    fn test_peak_magic_parallelism_ignores_clifford_rotations() {
+        use crate::angle::Angle;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+
+        let clifford = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        assert_eq!(peak_magic_parallelism(&[clifford]), 0);
+    }
+
+    #[test]
+    fn test_longest_anticommuting_chain_breaks_at_a_reset() {
+        // X-Z would anticommute, but the reset between them has no axis and
+        // so can't extend a run through it.
+        let ops = vec![
+            measurement(Pauli::X),
+            Operator::Reset { qubit: 0 },
+            measurement(Pauli::Z),
+        ];
+        assert_eq!(longest_anticommuting_chain(&ops), 1);
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use std::ops::{Add, Neg};
+
+use crate::mod8::Mod8;
+
+/// A rotation angle. `PiOver8(n)` means `n * pi / 8`; `Arbitrary(a)` is any
+/// other angle, stored as `a` where the rotation is `2 * a` radians (see
+/// `extract_angle` for why the halving convention is used).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Angle {
+    PiOver8(Mod8),
+    Arbitrary(f64),
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        match self {
+            Angle::PiOver8(m) => Angle::PiOver8(-m),
+            Angle::Arbitrary(a) => Angle::Arbitrary(-a),
+        }
+    }
+}
+
+/// Adds two rotation angles -- the core primitive for merging two same-axis
+/// rotations into one. Two `PiOver8`s add their numerators in `Mod8`,
+/// staying exact; two `Arbitrary`s add their radian values directly; a
+/// `PiOver8` mixed with an `Arbitrary` is promoted to radians via
+/// `to_radians` first, so the result is always `Arbitrary`.
+impl Add for Angle {
+    type Output = Angle;
+
+    fn add(self, rhs: Angle) -> Angle {
+        match (self, rhs) {
+            (Angle::PiOver8(a), Angle::PiOver8(b)) => Angle::PiOver8(a + b),
+            (Angle::Arbitrary(a), Angle::Arbitrary(b)) => Angle::Arbitrary(a + b),
+            (a, b) => Angle::Arbitrary(a.to_radians() + b.to_radians()),
+        }
+    }
+}
+
+/// How close a radian value must land to a multiple of `pi/8` for
+/// `Angle::from_radians` to snap it to a `PiOver8`, rather than keeping it
+/// as an `Arbitrary`.
+const FROM_RADIANS_EPSILON: f64 = 1e-9;
+
+impl Angle {
+    /// The angle's internal `a` value in radians: `n * pi / 8` for
+    /// `PiOver8(n)`, or `a` itself for `Arbitrary` -- both already use this
+    /// same "half the actual rotation" convention (see the type's doc
+    /// comment), so neither variant needs any further conversion.
+    pub fn to_radians(&self) -> f64 {
+        match self {
+            Angle::PiOver8(m) => m.to_u32() as f64 * std::f64::consts::PI / 8.0,
+            Angle::Arbitrary(a) => *a,
+        }
+    }
+
+    /// The inverse of `to_radians`: snaps `radians` to the nearest
+    /// `PiOver8(Mod8)` when it's within `FROM_RADIANS_EPSILON` of one
+    /// (reducing modulo a full turn of `a`, i.e. modulo `8` eighths of
+    /// pi, the same way `extract_angle` reduces a `pi`-fraction literal),
+    /// and otherwise returns `Arbitrary(radians)` unchanged.
+    pub fn from_radians(radians: f64) -> Angle {
+        let eighths = radians / (std::f64::consts::PI / 8.0);
+        let n = eighths.round();
+        if (eighths - n).abs() < FROM_RADIANS_EPSILON / (std::f64::consts::PI / 8.0) {
+            let n = n.rem_euclid(8.0) as u32;
+            Angle::PiOver8(Mod8::from(n))
+        } else {
+            Angle::Arbitrary(radians)
+        }
+    }
+}
+
+/// Displays `PiOver8(n)` with `n` in `0..8`, e.g. `PiOver8(6)`. The
+/// alternate form (`{:#}`) instead shows `n`'s canonical numerator (see
+/// `Mod8::canonical_numerator`), e.g. `-PiOver8(2)` for the same angle --
+/// useful wherever `PiOver8(6)` and `-PiOver8(2)` being the same rotation
+/// would otherwise look like an inconsistency to a reader.
+impl fmt::Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Angle::PiOver8(m) if f.alternate() => {
+                let n = m.canonical_numerator();
+                if n < 0 {
+                    write!(f, "-PiOver8({})", -n)
+                } else {
+                    write!(f, "PiOver8({})", n)
+                }
+            }
+            Angle::PiOver8(m) => write!(f, "PiOver8({})", m.to_u32()),
+            Angle::Arbitrary(a) => write!(f, "Arbitrary({})", a),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Angle::PiOver8(Mod8::Two), Angle::PiOver8(Mod8::Six));
+        assert_eq!(-Angle::Arbitrary(0.5), Angle::Arbitrary(-0.5));
+    }
+
+    #[test]
+    fn test_add_piover8_pairs_in_mod8_with_wraparound() {
+        assert_eq!(Angle::PiOver8(Mod8::Two) + Angle::PiOver8(Mod8::Three), Angle::PiOver8(Mod8::Five));
+        assert_eq!(Angle::PiOver8(Mod8::Six) + Angle::PiOver8(Mod8::Four), Angle::PiOver8(Mod8::Two));
+    }
+
+    #[test]
+    fn test_add_arbitrary_pairs_sum_their_radians() {
+        assert_eq!(Angle::Arbitrary(0.25) + Angle::Arbitrary(0.5), Angle::Arbitrary(0.75));
+    }
+
+    #[test]
+    fn test_add_mixed_piover8_and_arbitrary_promotes_to_radians() {
+        let sum = Angle::PiOver8(Mod8::Two) + Angle::Arbitrary(0.1);
+        assert_eq!(sum, Angle::Arbitrary(Angle::PiOver8(Mod8::Two).to_radians() + 0.1));
+    }
+
+    #[test]
+    fn test_display_shows_the_raw_numerator() {
+        assert_eq!(Angle::PiOver8(Mod8::Six).to_string(), "PiOver8(6)");
+        assert_eq!(Angle::PiOver8(Mod8::Two).to_string(), "PiOver8(2)");
+        assert_eq!(Angle::Arbitrary(0.5).to_string(), "Arbitrary(0.5)");
+    }
+
+    #[test]
+    fn test_to_radians_and_from_radians_round_trip_for_every_mod8_value() {
+        for m in [
+            Mod8::Zero,
+            Mod8::One,
+            Mod8::Two,
+            Mod8::Three,
+            Mod8::Four,
+            Mod8::Five,
+            Mod8::Six,
+            Mod8::Seven,
+        ] {
+            let angle = Angle::PiOver8(m);
+            assert_eq!(angle.to_radians(), m.to_u32() as f64 * std::f64::consts::PI / 8.0);
+            assert_eq!(Angle::from_radians(angle.to_radians()), angle);
+        }
+    }
+
+    #[test]
+    fn test_from_radians_keeps_an_off_grid_value_as_arbitrary() {
+        assert_eq!(Angle::from_radians(0.123), Angle::Arbitrary(0.123));
+        assert_eq!(Angle::Arbitrary(0.123).to_radians(), 0.123);
+    }
+
+    #[test]
+    fn test_display_alternate_shows_the_canonical_numerator_for_every_mod8_value() {
+        let cases = [
+            (Mod8::Zero, "PiOver8(0)"),
+            (Mod8::One, "PiOver8(1)"),
+            (Mod8::Two, "PiOver8(2)"),
+            (Mod8::Three, "PiOver8(3)"),
+            (Mod8::Four, "PiOver8(4)"),
+            (Mod8::Five, "-PiOver8(3)"),
+            (Mod8::Six, "-PiOver8(2)"),
+            (Mod8::Seven, "-PiOver8(1)"),
+        ];
+        for (m, expected) in cases {
+            assert_eq!(format!("{:#}", Angle::PiOver8(m)), expected);
+        }
+    }
+}
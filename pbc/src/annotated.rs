@@ -0,0 +1,100 @@
+//! A memoized wrapper around `Operator` for passes that repeatedly ask
+//! "is this Clifford?" or "what's its weight/support?" on the same
+//! circuit -- e.g. scheduling or statistics passes run after
+//! `spc_translation`. `extract` still produces plain `Operator`s; call
+//! [`annotate_operators`] on its output when a pass needs the cached
+//! classification.
+
+use crate::operator::Operator;
+use crate::pauli::Pauli;
+
+/// An `Operator` alongside its `is_clifford`, `weight` (the number of
+/// non-identity positions in its axis), and `support` (their indices),
+/// computed once instead of on every downstream query.
+///
+/// A `Reset` has no axis; it's treated as acting with weight 1 on just
+/// its own qubit, since that's the only qubit it touches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotatedOperator {
+    pub operator: Operator,
+    pub is_clifford: bool,
+    pub weight: usize,
+    pub support: Vec<usize>,
+}
+
+impl AnnotatedOperator {
+    pub fn new(operator: Operator) -> AnnotatedOperator {
+        let is_clifford = operator.is_clifford();
+        let support = match &operator {
+            Operator::Reset { qubit } => vec![*qubit],
+            Operator::Barrier(qubits) => qubits.clone(),
+            _ => operator
+                .axis()
+                .expect("non-Reset, non-Barrier operators always have an axis")
+                .as_slice()
+                .iter()
+                .enumerate()
+                .filter(|(_, pauli)| **pauli != Pauli::I)
+                .map(|(index, _)| index)
+                .collect(),
+        };
+        let weight = support.len();
+        AnnotatedOperator { operator, is_clifford, weight, support }
+    }
+}
+
+/// Annotates every operator in `operators`, in order.
+pub fn annotate_operators(operators: &[Operator]) -> Vec<AnnotatedOperator> {
+    operators.iter().cloned().map(AnnotatedOperator::new).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Angle;
+    use crate::axis::Axis;
+    use crate::mod8::Mod8;
+    use crate::operator::PauliRotation;
+
+    #[test]
+    fn test_annotated_operator_matches_freshly_computed_values() {
+        let op = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_paulis(4, &[(0, Pauli::X), (2, Pauli::Z)]),
+            Angle::PiOver8(Mod8::One),
+        ));
+        let annotated = AnnotatedOperator::new(op.clone());
+
+        assert_eq!(annotated.is_clifford, op.is_clifford());
+        let expected_support: Vec<usize> = op
+            .axis()
+            .unwrap()
+            .as_slice()
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| **p != Pauli::I)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(annotated.support, expected_support);
+        assert_eq!(annotated.weight, expected_support.len());
+    }
+
+    #[test]
+    fn test_annotated_reset_has_weight_one_on_its_own_qubit() {
+        let annotated = AnnotatedOperator::new(Operator::Reset { qubit: 3 });
+        assert!(annotated.is_clifford);
+        assert_eq!(annotated.weight, 1);
+        assert_eq!(annotated.support, vec![3]);
+    }
+
+    #[test]
+    fn test_annotate_operators_preserves_order() {
+        let ops = vec![
+            Operator::Reset { qubit: 0 },
+            Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::Z), target: 0 },
+        ];
+        let annotated = annotate_operators(&ops);
+        assert_eq!(annotated.len(), 2);
+        assert_eq!(annotated[0].operator, ops[0]);
+        assert_eq!(annotated[1].operator, ops[1]);
+    }
+}
@@ -0,0 +1,364 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::pauli::Pauli;
+
+pub(crate) fn to_symplectic_bits(pauli: Pauli) -> (bool, bool) {
+    match pauli {
+        Pauli::I => (false, false),
+        Pauli::X => (true, false),
+        Pauli::Z => (false, true),
+        Pauli::Y => (true, true),
+    }
+}
+
+pub(crate) fn from_symplectic_bits(x: bool, z: bool) -> Pauli {
+    match (x, z) {
+        (false, false) => Pauli::I,
+        (true, false) => Pauli::X,
+        (false, true) => Pauli::Z,
+        (true, true) => Pauli::Y,
+    }
+}
+
+/// A multi-qubit Pauli string, e.g. `IXYZ`, without a sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Axis(Vec<Pauli>);
+
+impl Axis {
+    pub fn new(paulis: Vec<Pauli>) -> Axis {
+        Axis(paulis)
+    }
+
+    /// An all-identity axis of the given width.
+    pub fn identity(width: usize) -> Axis {
+        Axis(vec![Pauli::I; width])
+    }
+
+    /// An axis with `pauli` at `index` and identity elsewhere.
+    pub fn new_with_pauli(width: usize, index: usize, pauli: Pauli) -> Axis {
+        let mut paulis = vec![Pauli::I; width];
+        paulis[index] = pauli;
+        Axis(paulis)
+    }
+
+    pub fn width(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn get(&self, index: usize) -> Pauli {
+        self.0[index]
+    }
+
+    pub fn as_slice(&self) -> &[Pauli] {
+        &self.0
+    }
+
+    /// Builds an axis with `entries` set to the given Paulis and identity
+    /// everywhere else.
+    pub(crate) fn new_with_paulis(width: usize, entries: &[(usize, Pauli)]) -> Axis {
+        let mut paulis = vec![Pauli::I; width];
+        for (index, pauli) in entries {
+            paulis[*index] = *pauli;
+        }
+        Axis(paulis)
+    }
+
+    /// Builds an axis from its symplectic `(x, z)` bit-pair representation:
+    /// `(0,0)->I`, `(1,0)->X`, `(0,1)->Z`, `(1,1)->Y`. Errors if `x_bits`
+    /// and `z_bits` have different lengths.
+    pub fn from_symplectic(x_bits: &[bool], z_bits: &[bool]) -> Result<Axis, String> {
+        if x_bits.len() != z_bits.len() {
+            return Err(format!(
+                "from_symplectic: x_bits has length {} but z_bits has length {}",
+                x_bits.len(),
+                z_bits.len()
+            ));
+        }
+        let paulis = x_bits.iter().zip(z_bits).map(|(&x, &z)| from_symplectic_bits(x, z)).collect();
+        Ok(Axis(paulis))
+    }
+
+    /// The inverse of [`Axis::from_symplectic`]: this axis's symplectic
+    /// `(x_bits, z_bits)` pair.
+    pub fn to_symplectic(&self) -> (Vec<bool>, Vec<bool>) {
+        self.0.iter().map(|&pauli| to_symplectic_bits(pauli)).unzip()
+    }
+
+    /// The Pauli group product of `self` and `other`, ignoring sign/phase:
+    /// positions are combined via the symplectic (x, z) XOR, which is all
+    /// that's needed to track how an axis moves under Clifford conjugation
+    /// when signs are not tracked (`SignedAxis` is the sign-tracking
+    /// counterpart).
+    pub(crate) fn multiply_ignoring_sign(&self, other: &Axis) -> Axis {
+        assert_eq!(self.width(), other.width());
+        let paulis = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| {
+                let (ax, az) = to_symplectic_bits(*a);
+                let (bx, bz) = to_symplectic_bits(*b);
+                from_symplectic_bits(ax ^ bx, az ^ bz)
+            })
+            .collect();
+        Axis(paulis)
+    }
+
+    /// Builds an axis from `slice`, erroring if its length doesn't match
+    /// `expected_width`. Useful for catching bugs in code that assembles
+    /// axes for a circuit of a known, fixed width.
+    pub fn from_slice_checked(slice: &[Pauli], expected_width: usize) -> Result<Axis, String> {
+        if slice.len() != expected_width {
+            return Err(format!(
+                "from_slice_checked: expected width {} but got {}",
+                expected_width,
+                slice.len()
+            ));
+        }
+        Ok(Axis(slice.to_vec()))
+    }
+
+    /// The Pauli sub-vector over `range`, as its own `Axis`. Useful for
+    /// inspecting how an operator acts on a block of qubits, e.g. a
+    /// sub-register, without the rest of the axis's identity padding.
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Axis {
+        Axis(self.0[range].to_vec())
+    }
+
+    /// This axis with its qubit order reversed, e.g. `IXYZ` becomes `ZYXI`.
+    /// Useful for converting between tools that index qubits big- vs
+    /// little-endian; reversing twice is the identity.
+    pub fn reversed(&self) -> Axis {
+        Axis(self.0.iter().rev().copied().collect())
+    }
+
+    /// Whether `self` and `other` commute as Pauli group elements. Two Pauli
+    /// strings commute iff they disagree (both non-identity, different) at an
+    /// even number of positions.
+    pub fn commutes_with(&self, other: &Axis) -> bool {
+        assert_eq!(self.width(), other.width());
+        let anticommuting_positions = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .filter(|(a, b)| **a != Pauli::I && **b != Pauli::I && a != b)
+            .count();
+        anticommuting_positions % 2 == 0
+    }
+}
+
+impl Axis {
+    /// Renders this axis as a string truncated to roughly `max_width`
+    /// characters, keeping a window around its non-identity support and
+    /// marking the cut points with `...`. Returns the full (untruncated)
+    /// string if it already fits within `max_width`. Intended for
+    /// human-readable terminal output on wide circuits; callers that need
+    /// the exact axis (e.g. for JSON output) should use `Display` instead.
+    pub fn to_truncated_string(&self, max_width: usize) -> String {
+        if self.width() <= max_width {
+            return self.to_string();
+        }
+
+        let support: Vec<usize> = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, pauli)| **pauli != Pauli::I)
+            .map(|(index, _)| index)
+            .collect();
+        let (first, last) = match (support.first(), support.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => (0, 0),
+        };
+
+        let available = max_width.saturating_sub(6).max(last - first + 1);
+        let center = (first + last) / 2;
+        let half = available / 2;
+        let start = center.saturating_sub(half);
+        let end = (start + available).min(self.width());
+        let start = end.saturating_sub(available);
+
+        let window: String = self.0[start..end]
+            .iter()
+            .map(|pauli| match pauli {
+                Pauli::I => 'I',
+                Pauli::X => 'X',
+                Pauli::Y => 'Y',
+                Pauli::Z => 'Z',
+            })
+            .collect();
+
+        let prefix = if start > 0 { "..." } else { "" };
+        let suffix = if end < self.width() { "..." } else { "" };
+        format!("{}{}{}", prefix, window, suffix)
+    }
+}
+
+impl FromStr for Axis {
+    type Err = String;
+
+    /// Parses the inverse of `Display`: a string of `I`/`X`/`Y`/`Z`
+    /// characters, one per qubit.
+    fn from_str(s: &str) -> Result<Axis, String> {
+        let paulis = s
+            .chars()
+            .map(|c| match c {
+                'I' => Ok(Pauli::I),
+                'X' => Ok(Pauli::X),
+                'Y' => Ok(Pauli::Y),
+                'Z' => Ok(Pauli::Z),
+                other => Err(format!("invalid Pauli character '{}' in axis '{}'", other, s)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Axis(paulis))
+    }
+}
+
+impl TryFrom<&str> for Axis {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Axis, String> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pauli in &self.0 {
+            let c = match pauli {
+                Pauli::I => 'I',
+                Pauli::X => 'X',
+                Pauli::Y => 'Y',
+                Pauli::Z => 'Z',
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_axis(s: &str) -> Axis {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_commutes_with() {
+        assert!(new_axis("X").commutes_with(&new_axis("X")));
+        assert!(!new_axis("X").commutes_with(&new_axis("Z")));
+        assert!(new_axis("XX").commutes_with(&new_axis("ZZ")));
+        assert!(new_axis("XI").commutes_with(&new_axis("IZ")));
+        assert!(new_axis("XZ").commutes_with(&new_axis("ZX")));
+        assert!(!new_axis("XZ").commutes_with(&new_axis("ZI")));
+    }
+
+    #[test]
+    fn test_reversed() {
+        assert_eq!(new_axis("IXYZ").reversed(), new_axis("ZYXI"));
+        assert_eq!(new_axis("IXYZ").reversed().reversed(), new_axis("IXYZ"));
+    }
+
+    #[test]
+    fn test_symplectic_round_trips_through_ixyz() {
+        let axis = new_axis("IXYZ");
+        let (x_bits, z_bits) = axis.to_symplectic();
+        assert_eq!(x_bits, vec![false, true, true, false]);
+        assert_eq!(z_bits, vec![false, false, true, true]);
+        assert_eq!(Axis::from_symplectic(&x_bits, &z_bits).unwrap(), axis);
+    }
+
+    #[test]
+    fn test_from_symplectic_errors_on_length_mismatch() {
+        assert!(Axis::from_symplectic(&[true, false], &[false]).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", new_axis("IXYZ")), "IXYZ");
+    }
+
+    #[test]
+    fn test_to_truncated_string_fits_within_max_width() {
+        assert_eq!(new_axis("IXYZ").to_truncated_string(10), "IXYZ");
+    }
+
+    #[test]
+    fn test_to_truncated_string_truncates_around_support() {
+        let mut paulis = vec![Pauli::I; 100];
+        paulis[40] = Pauli::X;
+        paulis[41] = Pauli::Y;
+        paulis[42] = Pauli::Z;
+        let axis = Axis::new(paulis);
+
+        let truncated = axis.to_truncated_string(20);
+        assert!(truncated.len() < 100);
+        assert!(truncated.contains("XYZ"));
+        assert!(truncated.starts_with("..."));
+        assert!(truncated.ends_with("..."));
+    }
+
+    #[test]
+    fn test_from_str_round_trips_with_display() {
+        assert_eq!("IXYZ".parse::<Axis>().unwrap(), new_axis("IXYZ"));
+        assert_eq!(new_axis("IXYZ").to_string().parse::<Axis>().unwrap(), new_axis("IXYZ"));
+    }
+
+    #[test]
+    fn test_from_str_rejects_invalid_characters() {
+        assert!("IXQZ".parse::<Axis>().is_err());
+    }
+
+    #[test]
+    fn test_try_from_str_accepts_valid_strings() {
+        assert_eq!(Axis::try_from("IXYZ").unwrap(), new_axis("IXYZ"));
+        assert_eq!(Axis::try_from("").unwrap(), Axis::new(vec![]));
+    }
+
+    #[test]
+    fn test_try_from_str_rejects_an_invalid_character() {
+        assert_eq!(
+            Axis::try_from("IXQ").unwrap_err(),
+            "invalid Pauli character 'Q' in axis 'IXQ'"
+        );
+    }
+
+    #[test]
+    fn test_from_slice_checked_errors_on_width_mismatch() {
+        assert!(Axis::from_slice_checked(&[Pauli::X, Pauli::Y], 3).is_err());
+    }
+
+    #[test]
+    fn test_from_slice_checked_succeeds_on_matching_width() {
+        assert_eq!(
+            Axis::from_slice_checked(&[Pauli::X, Pauli::Y], 2).unwrap(),
+            new_axis("XY")
+        );
+    }
+
+    #[test]
+    fn test_slice_extracts_a_qubit_range() {
+        assert_eq!(new_axis("IXYZ").slice(1..3), new_axis("XY"));
+        assert_eq!(new_axis("IXYZ").slice(0..4), new_axis("IXYZ"));
+    }
+
+    #[test]
+    fn test_multiply_ignoring_sign() {
+        assert_eq!(
+            new_axis("XI").multiply_ignoring_sign(&new_axis("IX")),
+            new_axis("XX")
+        );
+        assert_eq!(
+            new_axis("X").multiply_ignoring_sign(&new_axis("X")),
+            new_axis("I")
+        );
+        assert_eq!(
+            new_axis("X").multiply_ignoring_sign(&new_axis("Z")),
+            new_axis("Y")
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::fmt;
+
+use crate::analysis::identity_measurements;
+use crate::angle::Angle;
+use crate::frontend::ast::{Argument, GateCall};
+use crate::frontend::gate::translate_gate;
+use crate::operator::Operator;
+use crate::registers::Registers;
+use crate::spc::spc_translation;
+
+/// A fully-resolved circuit: a register layout plus the flat list of
+/// operators (rotations and measurements) to apply in order.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circuit {
+    pub registers: Registers,
+    pub operators: Vec<Operator>,
+}
+
+impl Circuit {
+    pub fn new(registers: Registers, operators: Vec<Operator>) -> Circuit {
+        Circuit { registers, operators }
+    }
+
+    /// Builds a circuit from operators assembled directly (e.g. decoded
+    /// from JSON), rather than translated from QASM: the counterpart to
+    /// `extract`. Unlike `new`, this validates that every operator fits
+    /// `registers` -- each rotation's or measurement's axis must have
+    /// exactly `registers.num_qubits()` entries, and each `Reset`'s qubit
+    /// must be in range -- so a circuit built this way can't silently
+    /// disagree with its own register layout.
+    pub fn from_operators(operators: Vec<Operator>, registers: Registers) -> Result<Circuit, String> {
+        let width = registers.num_qubits();
+        for (index, op) in operators.iter().enumerate() {
+            check_operator_width("from_operators", index, op, width)?;
+        }
+        Ok(Circuit { registers, operators })
+    }
+
+    /// Inserts `op` at `index`, shifting later operators back. Validates
+    /// `op` against `self.registers` the same way `from_operators` does
+    /// (axis width for a rotation/measurement, in-range qubit for a
+    /// reset), and that `index` is a valid insertion point (`0..=len()`),
+    /// so a circuit built up one operator at a time can't silently
+    /// disagree with its own register layout either.
+    pub fn insert_operator(&mut self, index: usize, op: Operator) -> Result<(), String> {
+        if index > self.operators.len() {
+            return Err(format!(
+                "insert_operator: index {} is out of range for {} operator(s)",
+                index,
+                self.operators.len()
+            ));
+        }
+        check_operator_width("insert_operator", index, &op, self.registers.num_qubits())?;
+        self.operators.insert(index, op);
+        Ok(())
+    }
+
+    /// Appends the operators for one gate call built programmatically,
+    /// rather than parsed from QASM text. `qubits` are flat qubit indices
+    /// into `self.registers`; `angles` are angle literals in the same
+    /// format QASM gate arguments use (e.g. `"pi/4"`). This reuses
+    /// `translate_gate` against `self.registers`, so the appended
+    /// operators are identical to parsing an equivalent QASM gate call.
+    pub fn append_gate(&mut self, name: &str, qubits: &[u32], angles: &[String]) -> Result<(), String> {
+        let qubits = qubits
+            .iter()
+            .map(|&q| {
+                let (register, index) = self
+                    .registers
+                    .qubit_name_and_index(q as usize)
+                    .ok_or_else(|| format!("qubit index {} is out of range", q))?;
+                Ok(Argument::Indexed(register.to_string(), index))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let call = GateCall { name: name.to_string(), angles: angles.to_vec(), qubits };
+        let ops = translate_gate(&self.registers, &call)?;
+        self.operators.extend(ops);
+        Ok(())
+    }
+
+    /// The circuit's "magic" subcircuit: its non-Clifford rotations plus
+    /// its measurements, with every Clifford gate absorbed into the frame
+    /// it implicitly defines. This is exactly `spc_translation`'s output --
+    /// the artifact most callers actually want, since it's what's left
+    /// once the (free) Clifford part of the circuit is factored out.
+    pub fn magic_subcircuit(&self) -> Vec<Operator> {
+        spc_translation(&self.operators)
+    }
+
+    /// Runs every consistency check this crate knows about against the
+    /// circuit -- the per-operator width check `from_operators` and
+    /// `insert_operator` already enforce, non-finite `Arbitrary` angles,
+    /// and all-identity measurements -- and reports every problem found,
+    /// rather than stopping at the first one. Useful as a one-shot
+    /// diagnostics report for a circuit assembled or deserialized some
+    /// other way, where `from_operators`'s fail-fast behavior would only
+    /// show a caller one problem at a time.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let width = self.registers.num_qubits();
+        let mut errors = Vec::new();
+
+        for (index, op) in self.operators.iter().enumerate() {
+            if let Err(e) = check_operator_width("validate", index, op, width) {
+                errors.push(e);
+            }
+            if let Operator::PauliRotation(rotation) = op {
+                if let Angle::Arbitrary(a) = rotation.angle {
+                    if !a.is_finite() {
+                        errors.push(format!("validate: operator {} has a non-finite angle ({})", index, a));
+                    }
+                }
+            }
+        }
+
+        for index in identity_measurements(&self.operators) {
+            errors.push(format!("validate: operator {} is an all-identity measurement", index));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Prints the register layout followed by each operator with its index, one
+/// per line, e.g.:
+///
+/// ```text
+/// registers: 2 qubit(s), 0 classical bit(s)
+///   qreg q[2]
+/// 0: rotation ZI PiOver8(2)
+/// 1: rotation IX PiOver8(1)
+/// ```
+///
+/// A stable, canonical textual dump of a circuit, suitable for logging and
+/// golden-file snapshot tests.
+impl fmt::Display for Circuit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "registers: {} qubit(s), {} classical bit(s)",
+            self.registers.num_qubits(),
+            self.registers.num_cbits()
+        )?;
+        for (name, size) in self.registers.qubit_registers() {
+            writeln!(f, "  qreg {}[{}]", name, size)?;
+        }
+        for (name, size) in self.registers.cbit_registers() {
+            writeln!(f, "  creg {}[{}]", name, size)?;
+        }
+        for (index, op) in self.operators.iter().enumerate() {
+            writeln!(f, "{}: {}", index, op)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `op` fits a circuit with `width` qubits: a rotation's or
+/// measurement's axis must have exactly `width` entries, and a reset's or
+/// barrier's qubits must be in range. `caller` and `index` are only used to
+/// label the error message.
+fn check_operator_width(caller: &str, index: usize, op: &Operator, width: usize) -> Result<(), String> {
+    match op {
+        Operator::Reset { qubit } if *qubit >= width => Err(format!(
+            "{}: operator {} resets qubit {}, out of range for {} qubit(s)",
+            caller, index, qubit, width
+        )),
+        Operator::Reset { .. } => Ok(()),
+        Operator::Barrier(qubits) => match qubits.iter().find(|&&qubit| qubit >= width) {
+            Some(qubit) => Err(format!(
+                "{}: operator {} is a barrier on qubit {}, out of range for {} qubit(s)",
+                caller, index, qubit, width
+            )),
+            None => Ok(()),
+        },
+        _ => {
+            let axis_width = op.axis().expect("non-Reset operators have an axis").width();
+            if axis_width != width {
+                Err(format!(
+                    "{}: operator {} has axis width {}, but the registers declare {} qubit(s)",
+                    caller, index, axis_width, width
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::{extract, parser::parse};
+
+    #[test]
+    fn test_append_gate_matches_the_qasm_parsed_equivalent() {
+        let source = "OPENQASM 2.0;\nqreg q[2];\nh q[0];\ncx q[0],q[1];\n";
+        let nodes = parse(source).unwrap();
+        let expected = extract(&nodes).unwrap();
+
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let mut circuit = Circuit::new(registers, Vec::new());
+        circuit.append_gate("h", &[0], &[]).unwrap();
+        circuit.append_gate("cx", &[0, 1], &[]).unwrap();
+
+        assert_eq!(circuit.operators, expected.operators);
+    }
+
+    #[test]
+    fn test_append_gate_rejects_an_out_of_range_qubit() {
+        let mut circuit = Circuit::new(Registers::new(), Vec::new());
+        assert!(circuit.append_gate("x", &[0], &[]).is_err());
+    }
+
+    #[test]
+    fn test_from_operators_accepts_operators_matching_the_register_width() {
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let mut expected = Circuit::new(registers.clone(), Vec::new());
+        expected.append_gate("h", &[0], &[]).unwrap();
+        expected.append_gate("cx", &[0, 1], &[]).unwrap();
+
+        let circuit = Circuit::from_operators(expected.operators.clone(), registers).unwrap();
+
+        assert_eq!(circuit.operators, expected.operators);
+    }
+
+    #[test]
+    fn test_from_operators_rejects_an_operator_whose_support_exceeds_the_register_width() {
+        let mut wide_registers = Registers::new();
+        wide_registers.add_qubit_register("q", 3);
+        let mut wide = Circuit::new(wide_registers, Vec::new());
+        wide.append_gate("cx", &[0, 1], &[]).unwrap();
+
+        let mut narrow_registers = Registers::new();
+        narrow_registers.add_qubit_register("q", 2);
+
+        assert!(Circuit::from_operators(wide.operators, narrow_registers).is_err());
+    }
+
+    #[test]
+    fn test_insert_operator_inserts_a_rotation_mid_circuit() {
+        use crate::angle::Angle;
+        use crate::axis::Axis;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+        use crate::pauli::Pauli;
+
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let first = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        let last = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 1, Pauli::X),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        let mut circuit = Circuit::new(registers, vec![first.clone(), last.clone()]);
+
+        let middle = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 0, Pauli::X),
+            Angle::PiOver8(Mod8::One),
+        ));
+        circuit.insert_operator(1, middle.clone()).unwrap();
+
+        assert_eq!(circuit.operators, vec![first, middle, last]);
+    }
+
+    #[test]
+    fn test_insert_operator_rejects_a_mismatched_width_operator() {
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let mut circuit = Circuit::new(registers, Vec::new());
+
+        let mut wide_registers = Registers::new();
+        wide_registers.add_qubit_register("q", 3);
+        let mut wide = Circuit::new(wide_registers, Vec::new());
+        wide.append_gate("cx", &[0, 1], &[]).unwrap();
+
+        assert!(circuit.insert_operator(0, wide.operators.remove(0)).is_err());
+        assert!(circuit.operators.is_empty());
+    }
+
+    #[test]
+    fn test_display_produces_a_stable_multi_line_dump() {
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        registers.add_cbit_register("c", 1);
+        let mut circuit = Circuit::new(registers, Vec::new());
+        circuit.append_gate("x", &[0], &[]).unwrap();
+
+        assert_eq!(
+            circuit.to_string(),
+            "registers: 2 qubit(s), 1 classical bit(s)\n\
+             \x20 qreg q[2]\n\
+             \x20 creg c[1]\n\
+             0: rotation XI PiOver8(4)\n"
+        );
+    }
+
+    #[test]
+    fn test_magic_subcircuit_matches_spc_translation_for_a_cx_circuit() {
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let mut circuit = Circuit::new(registers, Vec::new());
+        circuit.append_gate("cx", &[0, 1], &[]).unwrap();
+
+        assert_eq!(circuit.magic_subcircuit(), spc_translation(&circuit.operators));
+    }
+
+    #[test]
+    fn test_validate_accepts_a_well_formed_circuit() {
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let mut circuit = Circuit::new(registers, Vec::new());
+        circuit.append_gate("cx", &[0, 1], &[]).unwrap();
+
+        assert_eq!(circuit.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_accumulates_every_problem_in_a_broken_circuit() {
+        use crate::axis::Axis;
+        use crate::mod8::Mod8;
+        use crate::operator::PauliRotation;
+        use crate::pauli::Pauli;
+
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let mut circuit = Circuit::new(registers, Vec::new());
+
+        // Wrong axis width (built for 3 qubits, registers declare 2).
+        circuit.operators.push(Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(3, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Two),
+        )));
+        // A non-finite Arbitrary angle.
+        circuit.operators.push(Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 0, Pauli::X),
+            Angle::Arbitrary(f64::NAN),
+        )));
+        // An all-identity measurement.
+        circuit
+            .operators
+            .push(Operator::Measurement { axis: Axis::new(vec![Pauli::I, Pauli::I]), target: 0 });
+
+        let errors = circuit.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_insert_operator_rejects_an_out_of_range_index() {
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 1);
+        let mut circuit = Circuit::new(registers, Vec::new());
+        assert!(circuit.insert_operator(1, Operator::Reset { qubit: 0 }).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use regex::Regex;
+
+use crate::angle::Angle;
+use crate::mod8::Mod8;
+
+/// Parses `pi`-fraction tokens: `pi`, `pi/4`, `3 * pi / 4`, and also
+/// `3pi/4` -- a coefficient written directly against `pi` with no `*`,
+/// since some generators emit angles that way. Also accepts `tau` (the
+/// full-period constant some QASM dialects use for `2*pi`) and its
+/// fractions, e.g. `tau/4`, by rewriting it to the equivalent `2*pi/n`
+/// coefficient before the usual `pi`-fraction handling.
+fn parse_pi_fraction(token: &str) -> Option<(bool, u32, u32)> {
+    let tau = Regex::new(r"^(-)?\s*tau\s*(?:/\s*(\d+)\s*)?$").unwrap();
+    if let Some(caps) = tau.captures(token) {
+        let neg = caps.get(1).is_some();
+        let denom: u32 = caps.get(2).map_or(1, |m| m.as_str().parse().unwrap());
+        return Some((neg, 2, denom));
+    }
+    let with_coeff = Regex::new(r"^(-)?\s*(\d+)\s*\*?\s*pi\s*(?:/\s*(\d+)\s*)?$").unwrap();
+    if let Some(caps) = with_coeff.captures(token) {
+        let neg = caps.get(1).is_some();
+        let coeff: u32 = caps[2].parse().unwrap();
+        let denom: u32 = caps.get(3).map_or(1, |m| m.as_str().parse().unwrap());
+        return Some((neg, coeff, denom));
+    }
+    let without_coeff = Regex::new(r"^(-)?\s*pi\s*(?:/\s*(\d+)\s*)?$").unwrap();
+    if let Some(caps) = without_coeff.captures(token) {
+        let neg = caps.get(1).is_some();
+        let denom: u32 = caps.get(2).map_or(1, |m| m.as_str().parse().unwrap());
+        return Some((neg, 1, denom));
+    }
+    None
+}
+
+/// Whether `token` is some numeric spelling of zero (`0`, `-0`, `0.0`,
+/// `-0.0`, ...), checked ahead of `parse_pi_fraction`/`parse_arbitrary` so
+/// every such spelling normalizes to the same `PiOver8(Zero)` rather than
+/// a sign- or representation-dependent `Arbitrary(-0.0)`.
+fn is_zero_literal(token: &str) -> bool {
+    let re = Regex::new(r"^-?\d+(\.\d+)?$").unwrap();
+    re.is_match(token) && token.parse::<f64>().map(|value| value == 0.0).unwrap_or(false)
+}
+
+/// Parses a bare decimal or integer literal (`"1.25"`, `"-3"`, ...) as a
+/// radian value, with an optional leading sign.
+fn parse_arbitrary(token: &str) -> Option<f64> {
+    let re = Regex::new(r"^(-)?(\d+(?:\.\d+)?)$").unwrap();
+    re.captures(token).map(|caps| {
+        let value: f64 = caps[2].parse().unwrap();
+        if caps.get(1).is_some() {
+            -value
+        } else {
+            value
+        }
+    })
+}
+
+/// Parses an angle argument string (e.g. `"pi/4"`, `"-pi"`, `"3 * pi / 4"`,
+/// `"-1.25"`) for the gate `gate_name`, used only in error messages.
+///
+/// `Angle::Arbitrary` values are stored halved: the returned value `a`
+/// means the rotation itself is `2 * a` radians. This matches the
+/// convention used everywhere else an `Angle` is constructed.
+///
+/// A `pi`-fraction literal is reduced modulo `8` eighths of pi (i.e.
+/// modulo a full `2*pi` rotation) before becoming a `Mod8`, so large
+/// multiples like `"8 * pi"` or `"16 * pi / 2"` resolve to `PiOver8(Zero)`
+/// rather than erroring -- only a literal that isn't representable as a
+/// multiple of `pi/8` at all (e.g. `"pi/3"`) is rejected. `tau` (and its
+/// fractions, e.g. `"tau/4"`) is accepted as an alias for `2*pi`.
+pub fn extract_angle(token: &str, gate_name: &str) -> Result<Angle, String> {
+    let token = token.trim();
+    if is_zero_literal(token) {
+        return Ok(Angle::PiOver8(Mod8::Zero));
+    }
+
+    if let Some((neg, coeff, denom)) = parse_pi_fraction(token) {
+        // `extract_angle` returns the internal (halved) rotation angle, so a
+        // QASM angle of `coeff * pi / denom` becomes `coeff * pi / (2 * denom)`,
+        // i.e. numerator `4 * coeff / denom` eighths of pi.
+        if denom == 0 || (4 * coeff) % denom != 0 {
+            return Err(format!(
+                "{}: angle '{}' is not representable as a multiple of pi/8",
+                gate_name, token
+            ));
+        }
+        let mut n = (4 * coeff / denom) % 8;
+        if neg {
+            n = (8 - n) % 8;
+        }
+        return Ok(Angle::PiOver8(Mod8::from(n)));
+    }
+
+    if let Some(value) = parse_arbitrary(token) {
+        return Ok(Angle::Arbitrary(value / 2.0));
+    }
+
+    Err(format!("{}: invalid angle literal: '{}'", gate_name, token))
+}
+
+/// Like `extract_angle`, but also flags a `pi`-fraction literal whose
+/// pre-reduction coefficient implies more than one full `2*pi` rotation --
+/// e.g. `rz(3*pi)` is valid QASM but likely a mistake, since the crate
+/// reduces every angle modulo `2*pi` and would otherwise silently turn it
+/// into `rz(pi)` with no trace of the original value. Non-`pi`-fraction
+/// literals (`Arbitrary` angles, zero spellings) are never flagged, since
+/// they carry no notion of "pre-reduction".
+pub fn extract_angle_with_warning(token: &str, gate_name: &str) -> Result<(Angle, Option<String>), String> {
+    let angle = extract_angle(token, gate_name)?;
+    let warning = parse_pi_fraction(token.trim()).and_then(|(_, coeff, denom)| {
+        if denom == 0 || 4 * coeff / denom <= 8 {
+            return None;
+        }
+        Some(format!(
+            "{}: angle '{}' is more than a full rotation; reduced to {}",
+            gate_name, token, angle
+        ))
+    });
+    Ok((angle, warning))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_angle() {
+        assert_eq!(extract_angle("0", "t"), Ok(Angle::PiOver8(Mod8::Zero)));
+        assert_eq!(extract_angle("pi", "t"), Ok(Angle::PiOver8(Mod8::Four)));
+        assert_eq!(extract_angle("-pi", "t"), Ok(Angle::PiOver8(Mod8::Four)));
+        assert_eq!(extract_angle("pi/4", "t"), Ok(Angle::PiOver8(Mod8::One)));
+        assert_eq!(extract_angle("-pi/2", "t"), Ok(Angle::PiOver8(Mod8::Six)));
+        assert_eq!(extract_angle("3 * pi / 4", "t"), Ok(Angle::PiOver8(Mod8::Three)));
+        assert_eq!(extract_angle("-1.25", "t"), Ok(Angle::Arbitrary(-0.625)));
+        assert_eq!(extract_angle("1.25", "t"), Ok(Angle::Arbitrary(0.625)));
+        assert_eq!(extract_angle("2", "t"), Ok(Angle::Arbitrary(1.0)));
+        assert_eq!(extract_angle("-3", "t"), Ok(Angle::Arbitrary(-1.5)));
+    }
+
+    #[test]
+    fn test_extract_angle_reduces_large_pi_multiples_modulo_a_full_rotation() {
+        assert_eq!(extract_angle("8 * pi", "t"), Ok(Angle::PiOver8(Mod8::Zero)));
+        assert_eq!(extract_angle("2 * pi", "t"), Ok(Angle::PiOver8(Mod8::Zero)));
+        assert_eq!(extract_angle("16 * pi / 2", "t"), Ok(Angle::PiOver8(Mod8::Zero)));
+    }
+
+    #[test]
+    fn test_extract_angle_accepts_tau_as_an_alias_for_2_pi() {
+        assert_eq!(extract_angle("tau", "t"), Ok(Angle::PiOver8(Mod8::Zero)));
+        assert_eq!(extract_angle("tau/2", "t"), Ok(Angle::PiOver8(Mod8::Four)));
+        assert_eq!(extract_angle("tau/4", "t"), Ok(Angle::PiOver8(Mod8::Two)));
+        assert_eq!(extract_angle("-tau/4", "t"), Ok(Angle::PiOver8(Mod8::Six)));
+    }
+
+    #[test]
+    fn test_extract_angle_accepts_a_coefficient_written_directly_against_pi() {
+        assert_eq!(extract_angle("3pi/4", "t"), extract_angle("3 * pi / 4", "t"));
+        assert_eq!(extract_angle("2pi", "t"), extract_angle("2 * pi", "t"));
+        assert_eq!(extract_angle("-3pi/4", "t"), extract_angle("-3 * pi / 4", "t"));
+    }
+
+    #[test]
+    fn test_extract_angle_zero_spellings() {
+        for token in ["0", "-0", "0.0", "-0.0", " 0 ", " -0 "] {
+            assert_eq!(extract_angle(token, "t"), Ok(Angle::PiOver8(Mod8::Zero)), "token: '{}'", token);
+        }
+    }
+
+    #[test]
+    fn test_extract_angle_errors() {
+        assert!(extract_angle("banana", "t").is_err());
+        assert!(extract_angle("pi/3", "t").is_err());
+    }
+
+    #[test]
+    fn test_extract_angle_with_warning_flags_more_than_a_full_rotation() {
+        let (angle, warning) = extract_angle_with_warning("5*pi/2", "rz").unwrap();
+        assert_eq!(angle, Angle::PiOver8(Mod8::Two));
+        let warning = warning.expect("5*pi/2 is more than 2*pi and should warn");
+        assert!(warning.contains("rz"));
+        assert!(warning.contains("5*pi/2"));
+
+        let (angle, warning) = extract_angle_with_warning("3*pi", "rz").unwrap();
+        assert_eq!(angle, Angle::PiOver8(Mod8::Four));
+        assert!(warning.is_some());
+    }
+
+    #[test]
+    fn test_extract_angle_with_warning_is_silent_within_one_rotation() {
+        for token in ["pi", "-pi", "pi/4", "3 * pi / 4", "-1.25", "0"] {
+            let (_, warning) = extract_angle_with_warning(token, "rz").unwrap();
+            assert!(warning.is_none(), "token: '{}'", token);
+        }
+    }
+}
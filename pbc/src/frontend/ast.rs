@@ -0,0 +1,44 @@
+/// A qubit or classical-bit reference in a QASM source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Argument {
+    /// `q[3]`.
+    Indexed(String, usize),
+    /// `q`, i.e. the whole register, used for broadcast gate application.
+    Register(String),
+}
+
+/// A gate application, e.g. `rz(pi/2) q[0];` or `cx q[0], q[1];`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateCall {
+    pub name: String,
+    pub angles: Vec<String>,
+    pub qubits: Vec<Argument>,
+}
+
+/// A single top-level statement of a parsed QASM source file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AstNode {
+    QReg(String, usize),
+    CReg(String, usize),
+    ApplyGate(GateCall),
+    Measure { qubit: Argument, cbit: Argument },
+    /// `mpp "XZZX" q[0],q[1],q[2],q[3] -> c[0];`: a native multi-qubit
+    /// Pauli product measurement, reporting the joint parity of `pauli`'s
+    /// axis over `qubits` into `cbit`.
+    Mpp { pauli: String, qubits: Vec<Argument>, cbit: Argument },
+    /// `reset q[0];`: reinitializes a qubit to a known state.
+    Reset(Argument),
+    /// `barrier q[0],q[1];`: a scheduling fence over the listed qubits,
+    /// carrying no quantum operation of its own.
+    Barrier(Vec<Argument>),
+    /// `gate name(params) qargs { body }`: a user-defined gate. `qargs` and
+    /// `params` name the formal qubit and angle arguments the `body` gate
+    /// calls are written in terms of (as bare `Argument::Register`s and
+    /// angle strings, respectively); see `gate::expand_gate` for how a call
+    /// site substitutes its actual arguments in.
+    Gate { name: String, params: Vec<String>, qargs: Vec<String>, body: Vec<GateCall> },
+    /// `if (c==1) x q[0];`: applies `call` only when classical register
+    /// `creg`'s value equals `value`, producing `Operator::Conditional`s
+    /// over `creg`'s bits.
+    If { creg: String, value: u64, call: GateCall },
+}
@@ -0,0 +1,415 @@
+use super::ast::{Argument, AstNode};
+use super::gate::{
+    expand_gate, extract_cbit, extract_qubit, translate_gate_with_warnings, translate_mpp, GateDef, GateTable,
+};
+use crate::angle::Angle;
+use crate::axis::Axis;
+use crate::circuit::Circuit;
+use crate::operator::Operator;
+use crate::pauli::Pauli;
+use crate::registers::Registers;
+
+/// Options controlling how `extract` translates a parsed QASM AST into a
+/// `Circuit`. Defaults (`ExtractOptions::default()`) match `extract`'s
+/// unconfigured behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractOptions {
+    /// If true, any gate that translates to an `Angle::Arbitrary` rotation
+    /// (i.e. not a multiple of pi/8) is rejected, naming the offending
+    /// gate. Useful for enforcing Clifford+T-only input on fault-tolerant
+    /// targets that can't execute arbitrary rotations.
+    pub reject_arbitrary: bool,
+    /// If true, `extract_with_warnings` collects a warning for any angle
+    /// gate whose literal implies more than a full `2*pi` rotation (see
+    /// `extract_angle_with_warning`), instead of silently reducing it.
+    /// Only top-level calls of the single-angle builtins are checked (see
+    /// `translate_gate_with_warnings`); calls inside a user-defined gate
+    /// body or under an `if` are not.
+    /// `extract_with_options`/`extract` never surface these, since they
+    /// have no way to return them.
+    pub warn_large_angles: bool,
+}
+
+/// Builds a [`Circuit`] from a parsed QASM AST: declares the registers,
+/// then translates each gate application and measurement in order.
+pub fn extract(nodes: &[AstNode]) -> Result<Circuit, String> {
+    extract_with_options(nodes, &ExtractOptions::default())
+}
+
+/// Like `extract`, but with [`ExtractOptions`] controlling how it reacts to
+/// input it would otherwise translate without complaint.
+pub fn extract_with_options(nodes: &[AstNode], options: &ExtractOptions) -> Result<Circuit, String> {
+    extract_with_warnings(nodes, options).map(|(circuit, _warnings)| circuit)
+}
+
+/// Like `extract_with_options`, but also returns the warnings collected
+/// along the way (currently only `options.warn_large_angles`'s, in node
+/// order), for a caller that wants to report them (e.g. `--warn-large-angles`).
+pub fn extract_with_warnings(nodes: &[AstNode], options: &ExtractOptions) -> Result<(Circuit, Vec<String>), String> {
+    let mut registers = Registers::new();
+    for node in nodes {
+        match node {
+            AstNode::QReg(name, size) => registers.add_qubit_register(name, *size),
+            AstNode::CReg(name, size) => registers.add_cbit_register(name, *size),
+            AstNode::ApplyGate(_)
+            | AstNode::Measure { .. }
+            | AstNode::Mpp { .. }
+            | AstNode::Reset(_)
+            | AstNode::Barrier(_)
+            | AstNode::Gate { .. }
+            | AstNode::If { .. } => {}
+        }
+    }
+
+    let mut gate_table: GateTable = GateTable::new();
+    for node in nodes {
+        if let AstNode::Gate { name, params, qargs, body } = node {
+            gate_table.insert(
+                name.clone(),
+                GateDef { params: params.clone(), qargs: qargs.clone(), body: body.clone() },
+            );
+        }
+    }
+
+    let mut operators = Vec::new();
+    let mut warnings = Vec::new();
+    for node in nodes {
+        match node {
+            AstNode::QReg(_, _) | AstNode::CReg(_, _) | AstNode::Gate { .. } => {}
+            AstNode::ApplyGate(call) => {
+                // User-defined gates don't go through `translate_gate_with_warnings`:
+                // `expand_gate` falls back to plain `translate_gate` for any name
+                // `gate_table` doesn't define, so builtins still get warnings.
+                let ops = if !gate_table.contains_key(&call.name) && options.warn_large_angles {
+                    let (ops, gate_warnings) = translate_gate_with_warnings(&registers, call)?;
+                    warnings.extend(gate_warnings);
+                    ops
+                } else {
+                    expand_gate(&gate_table, &registers, call)?
+                };
+                if options.reject_arbitrary {
+                    let has_arbitrary = ops.iter().any(|op| {
+                        matches!(op, Operator::PauliRotation(r) if matches!(r.angle, Angle::Arbitrary(_)))
+                    });
+                    if has_arbitrary {
+                        return Err(format!(
+                            "{}: arbitrary angle rotations are rejected by --reject-arbitrary",
+                            call.name
+                        ));
+                    }
+                }
+                operators.extend(ops);
+            }
+            AstNode::Measure {
+                qubit: Argument::Register(qname),
+                cbit: Argument::Register(cname),
+            } => {
+                let qsize = registers
+                    .qubit_register_size(qname)
+                    .ok_or_else(|| format!("unknown qubit register: {}", qname))?;
+                let csize = registers
+                    .cbit_register_size(cname)
+                    .ok_or_else(|| format!("unknown classical bit register: {}", cname))?;
+                if qsize != csize {
+                    return Err(format!(
+                        "measure {} -> {}: register size mismatch ({} qubit(s), {} classical bit(s))",
+                        qname, cname, qsize, csize
+                    ));
+                }
+                let width = registers.num_qubits();
+                for i in 0..qsize {
+                    let qubit = extract_qubit(&registers, &Argument::Indexed(qname.clone(), i))?;
+                    let target = extract_cbit(&registers, &Argument::Indexed(cname.clone(), i))?;
+                    operators.push(Operator::Measurement {
+                        axis: Axis::new_with_pauli(width, qubit, Pauli::Z),
+                        target,
+                    });
+                }
+            }
+            AstNode::Measure { qubit, cbit } => {
+                let width = registers.num_qubits();
+                let qubit = extract_qubit(&registers, qubit)?;
+                let target = extract_cbit(&registers, cbit)?;
+                operators.push(Operator::Measurement {
+                    axis: Axis::new_with_pauli(width, qubit, Pauli::Z),
+                    target,
+                });
+            }
+            AstNode::Mpp { pauli, qubits, cbit } => {
+                operators.push(translate_mpp(&registers, pauli, qubits, cbit)?);
+            }
+            AstNode::Reset(qubit) => {
+                let width = registers.num_qubits();
+                let qubit = extract_qubit(&registers, qubit)?;
+                // A hardware `reset` is a Z-basis measurement followed by
+                // a classically-controlled X that corrects a `1` outcome
+                // back to `0` -- exactly the state `Operator::Reset`
+                // already models, so the two are emitted back to back:
+                // the `Measurement` records the (discarded) outcome the
+                // correction would be conditioned on, and `Reset` carries
+                // the actual "fresh, known state" semantics downstream
+                // (e.g. clearing the SPC frame in `spc_translation`).
+                operators.push(Operator::Measurement {
+                    axis: Axis::new_with_pauli(width, qubit, Pauli::Z),
+                    target: qubit,
+                });
+                operators.push(Operator::Reset { qubit });
+            }
+            AstNode::Barrier(qubits) => {
+                let qubits = qubits
+                    .iter()
+                    .map(|qubit| extract_qubit(&registers, qubit))
+                    .collect::<Result<_, _>>()?;
+                operators.push(Operator::Barrier(qubits));
+            }
+            AstNode::If { creg, value, call } => {
+                let size = registers
+                    .cbit_register_size(creg)
+                    .ok_or_else(|| format!("unknown classical bit register: {}", creg))?;
+                let cbits = (0..size)
+                    .map(|i| extract_cbit(&registers, &Argument::Indexed(creg.clone(), i)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let ops = expand_gate(&gate_table, &registers, call)?;
+                if options.reject_arbitrary
+                    && ops.iter().any(|op| {
+                        matches!(op, Operator::PauliRotation(r) if matches!(r.angle, Angle::Arbitrary(_)))
+                    })
+                {
+                    return Err(format!(
+                        "{}: arbitrary angle rotations are rejected by --reject-arbitrary",
+                        call.name
+                    ));
+                }
+                for op in ops {
+                    operators.push(Operator::Conditional { cbits: cbits.clone(), value: *value, inner: Box::new(op) });
+                }
+            }
+        }
+    }
+
+    Ok((Circuit::new(registers, operators), warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse;
+
+    #[test]
+    fn test_extract_simple_circuit() {
+        let nodes = parse("qreg q[2];\ncreg c[2];\nh q[0];\ncx q[0],q[1];\nmeasure q[0] -> c[0];")
+            .unwrap();
+        let circuit = extract(&nodes).unwrap();
+        assert_eq!(circuit.registers.num_qubits(), 2);
+        assert_eq!(circuit.registers.num_cbits(), 2);
+        // 3 rotations for h, 3 for cx, 1 measurement.
+        assert_eq!(circuit.operators.len(), 7);
+        assert!(circuit.operators.last().unwrap().is_measurement());
+    }
+
+    #[test]
+    fn test_extract_reset_emits_a_measurement_followed_by_a_reset() {
+        let nodes = parse("qreg q[1];\nreset q[0];").unwrap();
+        let circuit = extract(&nodes).unwrap();
+        assert_eq!(
+            circuit.operators,
+            vec![
+                Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::Z), target: 0 },
+                Operator::Reset { qubit: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_barrier_between_rotations_is_preserved_in_the_op_list() {
+        let nodes = parse("qreg q[2];\nh q[0];\nbarrier q[0],q[1];\nh q[0];").unwrap();
+        let circuit = extract(&nodes).unwrap();
+        // 3 rotations for the first `h`, then the barrier, then 3 more for
+        // the second `h`.
+        assert_eq!(circuit.operators.len(), 7);
+        assert_eq!(circuit.operators[3], Operator::Barrier(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_extract_h_on_a_whole_register_broadcasts_to_every_qubit() {
+        let nodes = parse("qreg q[3];\nh q;").unwrap();
+        let circuit = extract(&nodes).unwrap();
+        // 3 rotations per qubit, 3 qubits.
+        assert_eq!(circuit.operators.len(), 9);
+    }
+
+    #[test]
+    fn test_extract_a_circuit_of_only_no_ops_produces_an_empty_op_list() {
+        let nodes = parse("qreg q[2];\nid q[0];\nu0(100) q[1];\ndelay[100] q[0];").unwrap();
+        let circuit = extract(&nodes).unwrap();
+        assert_eq!(circuit.operators, vec![]);
+    }
+
+    #[test]
+    fn test_extract_user_defined_gate_expands_identically_to_a_builtin() {
+        let nodes = parse("qreg q[2];\ngate mycx a,b { cx a,b; }\nmycx q[0],q[1];").unwrap();
+        let circuit = extract(&nodes).unwrap();
+        let builtin = parse("qreg q[2];\ncx q[0],q[1];").unwrap();
+        assert_eq!(circuit.operators, extract(&builtin).unwrap().operators);
+    }
+
+    #[test]
+    fn test_extract_user_defined_gate_with_angle_parameter_and_nested_call() {
+        let nodes = parse(
+            "qreg q[1];\n\
+             gate myrz(theta) a { rz(theta) a; }\n\
+             gate mynest(theta) a { myrz(theta) a; }\n\
+             mynest(pi/4) q[0];",
+        )
+        .unwrap();
+        let circuit = extract(&nodes).unwrap();
+        let builtin = parse("qreg q[1];\nrz(pi/4) q[0];").unwrap();
+        assert_eq!(circuit.operators, extract(&builtin).unwrap().operators);
+    }
+
+    #[test]
+    fn test_extract_unknown_user_defined_gate_is_a_clear_error() {
+        let nodes = parse("qreg q[1];\nnosuchgate q[0];").unwrap();
+        let err = extract(&nodes).unwrap_err();
+        assert!(err.contains("nosuchgate"), "error should name the unknown gate: {}", err);
+    }
+
+    #[test]
+    fn test_extract_user_defined_gate_arity_mismatch_is_an_error() {
+        let nodes = parse("qreg q[2];\ngate mycx a,b { cx a,b; }\nmycx q[0];").unwrap();
+        assert!(extract(&nodes).is_err());
+    }
+
+    #[test]
+    fn test_extract_if_produces_a_conditional_operator_referencing_the_condition_bit() {
+        let nodes = parse("qreg q[1];\ncreg c[1];\nif (c==1) x q[0];").unwrap();
+        let circuit = extract(&nodes).unwrap();
+        assert_eq!(
+            circuit.operators,
+            vec![Operator::Conditional {
+                cbits: vec![0],
+                value: 1,
+                inner: Box::new(Operator::PauliRotation(crate::operator::PauliRotation::new(
+                    Axis::new_with_pauli(1, 0, Pauli::X),
+                    Angle::PiOver8(crate::mod8::Mod8::Four),
+                ))),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_extract_unknown_qubit_is_error() {
+        let nodes = parse("qreg q[1];\nx q[1];").unwrap();
+        assert!(extract(&nodes).is_err());
+    }
+
+    #[test]
+    fn test_extract_two_mpp_circuit() {
+        let nodes = parse(
+            r#"
+            qreg q[4];
+            creg c[2];
+            mpp "XZZX" q[0],q[1],q[2],q[3] -> c[0];
+            mpp "YY" q[1],q[2] -> c[1];
+            "#,
+        )
+        .unwrap();
+        let circuit = extract(&nodes).unwrap();
+        assert_eq!(circuit.operators.len(), 2);
+        assert_eq!(
+            circuit.operators[0],
+            Operator::Measurement {
+                axis: crate::axis::Axis::new_with_paulis(
+                    4,
+                    &[
+                        (0, crate::pauli::Pauli::X),
+                        (1, crate::pauli::Pauli::Z),
+                        (2, crate::pauli::Pauli::Z),
+                        (3, crate::pauli::Pauli::X),
+                    ]
+                ),
+                target: 0,
+            }
+        );
+        assert_eq!(
+            circuit.operators[1],
+            Operator::Measurement {
+                axis: crate::axis::Axis::new_with_paulis(
+                    4,
+                    &[(1, crate::pauli::Pauli::Y), (2, crate::pauli::Pauli::Y)]
+                ),
+                target: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_register_wide_measure_expands_per_qubit() {
+        let nodes = parse("qreg q[2];\ncreg c[2];\nmeasure q -> c;").unwrap();
+        let circuit = extract(&nodes).unwrap();
+        assert_eq!(
+            circuit.operators,
+            vec![
+                Operator::Measurement {
+                    axis: crate::axis::Axis::new_with_pauli(2, 0, crate::pauli::Pauli::Z),
+                    target: 0,
+                },
+                Operator::Measurement {
+                    axis: crate::axis::Axis::new_with_pauli(2, 1, crate::pauli::Pauli::Z),
+                    target: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_register_wide_measure_with_mismatched_sizes_is_a_clear_error() {
+        let nodes = parse("qreg q[3];\ncreg c[2];\nmeasure q -> c;").unwrap();
+        let err = extract(&nodes).unwrap_err();
+        assert!(err.contains('3'), "error should name the qubit register size: {}", err);
+        assert!(err.contains('2'), "error should name the cbit register size: {}", err);
+    }
+
+    #[test]
+    fn test_reject_arbitrary_errors_on_an_arbitrary_angle_and_names_the_gate() {
+        let nodes = parse("qreg q[1];\nrz(-1.25) q[0];").unwrap();
+
+        assert!(extract(&nodes).is_ok());
+
+        let options = ExtractOptions { reject_arbitrary: true, ..ExtractOptions::default() };
+        let err = extract_with_options(&nodes, &options).unwrap_err();
+        assert!(err.contains("rz"), "error should name the offending gate: {}", err);
+    }
+
+    #[test]
+    fn test_extract_with_warnings_flags_a_large_rz_angle() {
+        let nodes = parse("qreg q[1];\nrz(5*pi/2) q[0];").unwrap();
+
+        let options = ExtractOptions { warn_large_angles: true, ..ExtractOptions::default() };
+        let (circuit, warnings) = extract_with_warnings(&nodes, &options).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("rz"));
+        assert_eq!(
+            circuit.operators,
+            vec![Operator::PauliRotation(crate::operator::PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(crate::mod8::Mod8::Two),
+            ))]
+        );
+
+        let without_flag = extract_with_warnings(&nodes, &ExtractOptions::default()).unwrap();
+        assert!(without_flag.1.is_empty());
+    }
+
+    #[test]
+    fn test_extract_mpp_length_mismatch_is_error() {
+        let nodes = parse(r#"qreg q[2];creg c[1];mpp "XZZ" q[0],q[1] -> c[0];"#).unwrap();
+        assert!(extract(&nodes).is_err());
+    }
+
+    #[test]
+    fn test_extract_mpp_invalid_pauli_character_is_error() {
+        let nodes = parse(r#"qreg q[2];creg c[1];mpp "XW" q[0],q[1] -> c[0];"#).unwrap();
+        assert!(extract(&nodes).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+pub mod angle;
+pub mod ast;
+pub mod extract;
+pub mod gate;
+pub mod parser;
+pub mod pauli_text;
+
+pub use extract::{extract, extract_with_options, extract_with_warnings, ExtractOptions};
+pub use parser::QasmVersion;
+pub use pauli_text::parse_pauli_text;
@@ -0,0 +1,456 @@
+use regex::Regex;
+
+use super::ast::{Argument, AstNode, GateCall};
+
+/// Which register-declaration syntax a source file uses: QASM 2's
+/// `qreg q[2]; creg c[2];`, or QASM 3's `qubit[2] q; bit[2] c;`. Everything
+/// else this parser recognizes (gate calls, `measure`, `mpp`) is shared
+/// between the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QasmVersion {
+    V2,
+    V3,
+}
+
+/// Parses a (small subset of) OpenQASM 2 source into a list of [`AstNode`].
+/// Equivalent to `parse_with_version(text, QasmVersion::V2)`.
+pub fn parse(text: &str) -> Result<Vec<AstNode>, String> {
+    parse_with_version(text, QasmVersion::V2)
+}
+
+/// Like `parse`, but for `QasmVersion::V3`, also recognizes OpenQASM 3's
+/// `qubit[n] name;` and `bit[n] name;` register declarations (the
+/// `size`-before-`name` order QASM 3 uses, as opposed to QASM 2's
+/// `name[size]`), mapping them onto the same `AstNode::QReg`/`AstNode::CReg`
+/// nodes QASM 2's `qreg`/`creg` produce.
+pub fn parse_with_version(text: &str, version: QasmVersion) -> Result<Vec<AstNode>, String> {
+    let mut nodes = Vec::new();
+    for statement in split_statements(text) {
+        let statement = strip_comment(&statement).trim().to_string();
+        let statement = statement.replace('\n', " ");
+        if statement.is_empty()
+            || statement.starts_with("OPENQASM")
+            || statement.starts_with("include")
+        {
+            continue;
+        }
+        if let Some(rest) = statement.strip_prefix("qreg") {
+            let (name, size) = parse_register_decl(rest)?;
+            nodes.push(AstNode::QReg(name, size));
+        } else if let Some(rest) = statement.strip_prefix("creg") {
+            let (name, size) = parse_register_decl(rest)?;
+            nodes.push(AstNode::CReg(name, size));
+        } else if version == QasmVersion::V3 && statement.starts_with("qubit") {
+            let (name, size) = parse_qasm3_decl(statement.strip_prefix("qubit").unwrap())?;
+            nodes.push(AstNode::QReg(name, size));
+        } else if version == QasmVersion::V3 && statement.starts_with("bit") {
+            let (name, size) = parse_qasm3_decl(statement.strip_prefix("bit").unwrap())?;
+            nodes.push(AstNode::CReg(name, size));
+        } else if let Some(rest) = statement.strip_prefix("measure") {
+            nodes.push(parse_measure(rest)?);
+        } else if let Some(rest) = statement.strip_prefix("mpp") {
+            nodes.push(parse_mpp(rest)?);
+        } else if let Some(rest) = statement.strip_prefix("reset") {
+            nodes.push(AstNode::Reset(parse_argument(rest)?));
+        } else if let Some(rest) = statement.strip_prefix("barrier") {
+            nodes.push(parse_barrier(rest)?);
+        } else if let Some(rest) = statement.strip_prefix("delay") {
+            nodes.push(AstNode::ApplyGate(parse_delay(rest)?));
+        } else if let Some(rest) = statement.strip_prefix("gate") {
+            nodes.push(parse_gate_def(rest)?);
+        } else if let Some(rest) = statement.strip_prefix("if") {
+            nodes.push(parse_if(rest)?);
+        } else {
+            nodes.push(AstNode::ApplyGate(parse_gate_call(&statement)?));
+        }
+    }
+    Ok(nodes)
+}
+
+/// Splits `text` into top-level statements on `;`, except inside a `{ ...
+/// }` block (a `gate` definition's body), which is kept as one statement
+/// regardless of the `;`s it contains -- so a multi-statement gate body
+/// survives as a single chunk for `parse_gate_def` to split on its own.
+fn split_statements(text: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for ch in text.chars() {
+        match ch {
+            '{' => {
+                depth += 1;
+                current.push(ch);
+            }
+            '}' => {
+                depth -= 1;
+                current.push(ch);
+                if depth == 0 {
+                    statements.push(std::mem::take(&mut current));
+                }
+            }
+            ';' if depth == 0 => statements.push(std::mem::take(&mut current)),
+            _ => current.push(ch),
+        }
+    }
+    if !current.trim().is_empty() {
+        statements.push(current);
+    }
+    statements
+}
+
+fn strip_comment(statement: &str) -> String {
+    statement
+        .lines()
+        .map(|line| match line.find("//") {
+            Some(i) => &line[..i],
+            None => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_register_decl(rest: &str) -> Result<(String, usize), String> {
+    let re = Regex::new(r"^\s*(\w+)\s*\[\s*(\d+)\s*\]\s*$").unwrap();
+    let caps = re
+        .captures(rest)
+        .ok_or_else(|| format!("invalid register declaration: {}", rest))?;
+    let name = caps[1].to_string();
+    let size: usize = caps[2].parse().unwrap();
+    Ok((name, size))
+}
+
+/// Parses the `[size] name` tail of a QASM 3 `qubit`/`bit` declaration,
+/// e.g. `[2] q` from `qubit[2] q;` -- the reverse order of QASM 2's
+/// `qreg`/`creg`, which give `name[size]`.
+fn parse_qasm3_decl(rest: &str) -> Result<(String, usize), String> {
+    let re = Regex::new(r"^\s*\[\s*(\d+)\s*\]\s*(\w+)\s*$").unwrap();
+    let caps = re
+        .captures(rest)
+        .ok_or_else(|| format!("invalid QASM 3 register declaration: {}", rest))?;
+    let size: usize = caps[1].parse().unwrap();
+    let name = caps[2].to_string();
+    Ok((name, size))
+}
+
+fn parse_argument(text: &str) -> Result<Argument, String> {
+    let text = text.trim();
+    let re = Regex::new(r"^(\w+)\s*\[\s*(\d+)\s*\]$").unwrap();
+    if let Some(caps) = re.captures(text) {
+        return Ok(Argument::Indexed(caps[1].to_string(), caps[2].parse().unwrap()));
+    }
+    let re_reg = Regex::new(r"^\w+$").unwrap();
+    if re_reg.is_match(text) {
+        return Ok(Argument::Register(text.to_string()));
+    }
+    Err(format!("invalid qubit/cbit argument: {}", text))
+}
+
+fn parse_measure(rest: &str) -> Result<AstNode, String> {
+    let (lhs, rhs) = rest
+        .split_once("->")
+        .ok_or_else(|| format!("invalid measure statement: measure{}", rest))?;
+    Ok(AstNode::Measure {
+        qubit: parse_argument(lhs)?,
+        cbit: parse_argument(rhs)?,
+    })
+}
+
+fn parse_mpp(rest: &str) -> Result<AstNode, String> {
+    let (lhs, rhs) = rest
+        .split_once("->")
+        .ok_or_else(|| format!("invalid mpp statement: mpp{}", rest))?;
+    let re = Regex::new(r#"^\s*"([^"]*)"\s+(.+)$"#).unwrap();
+    let caps = re
+        .captures(lhs)
+        .ok_or_else(|| format!("invalid mpp statement: mpp{}", lhs))?;
+    let pauli = caps[1].to_string();
+    let qubits: Vec<Argument> = caps[2]
+        .split(',')
+        .map(parse_argument)
+        .collect::<Result<_, _>>()?;
+    let cbit = parse_argument(rhs)?;
+    Ok(AstNode::Mpp { pauli, qubits, cbit })
+}
+
+fn parse_barrier(rest: &str) -> Result<AstNode, String> {
+    let qubits: Vec<Argument> = rest
+        .trim()
+        .split(',')
+        .map(parse_argument)
+        .collect::<Result<_, _>>()?;
+    Ok(AstNode::Barrier(qubits))
+}
+
+/// Parses `gate name(params) qargs { body }` (the `gate` keyword already
+/// stripped). `body` is itself a `;`-separated list of gate calls written
+/// in terms of the formal `params`/`qargs`; see `gate::expand_gate` for how
+/// a call site later substitutes its actual arguments into it.
+fn parse_gate_def(rest: &str) -> Result<AstNode, String> {
+    let re = Regex::new(r"(?s)^\s*(\w+)\s*(\(([^)]*)\))?\s*([^{]+)\{(.*)\}\s*$").unwrap();
+    let caps = re
+        .captures(rest)
+        .ok_or_else(|| format!("invalid gate definition: gate{}", rest))?;
+    let name = caps[1].to_string();
+    let params: Vec<String> = match caps.get(3) {
+        Some(m) if !m.as_str().trim().is_empty() => {
+            m.as_str().split(',').map(|s| s.trim().to_string()).collect()
+        }
+        _ => Vec::new(),
+    };
+    let qargs: Vec<String> = caps[4]
+        .trim()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let body = split_statements(&caps[5])
+        .into_iter()
+        .map(|stmt| strip_comment(&stmt).trim().replace('\n', " "))
+        .filter(|stmt| !stmt.is_empty())
+        .map(|stmt| parse_gate_call(&stmt))
+        .collect::<Result<_, _>>()?;
+    Ok(AstNode::Gate { name, params, qargs, body })
+}
+
+/// Parses `if (creg==value) call;` (the `if` keyword already stripped) into
+/// an `AstNode::If`. `call` is a single gate-call statement, parsed the
+/// same way a bare gate application would be.
+fn parse_if(rest: &str) -> Result<AstNode, String> {
+    let re = Regex::new(r"^\s*\(\s*(\w+)\s*==\s*(\d+)\s*\)\s*(.+)$").unwrap();
+    let caps = re
+        .captures(rest)
+        .ok_or_else(|| format!("invalid if statement: if{}", rest))?;
+    let creg = caps[1].to_string();
+    let value: u64 = caps[2].parse().unwrap();
+    let call = parse_gate_call(&caps[3])?;
+    Ok(AstNode::If { creg, value, call })
+}
+
+fn parse_gate_call(statement: &str) -> Result<GateCall, String> {
+    let re = Regex::new(r"^(\w+)\s*(\(([^)]*)\))?\s+(.+)$").unwrap();
+    let caps = re
+        .captures(statement)
+        .ok_or_else(|| format!("invalid gate statement: {}", statement))?;
+    let name = caps[1].to_string();
+    let angles: Vec<String> = match caps.get(3) {
+        Some(m) if !m.as_str().trim().is_empty() => {
+            m.as_str().split(',').map(|s| s.trim().to_string()).collect()
+        }
+        _ => Vec::new(),
+    };
+    let qubits: Vec<Argument> = caps[4]
+        .split(',')
+        .map(parse_argument)
+        .collect::<Result<_, _>>()?;
+    Ok(GateCall { name, angles, qubits })
+}
+
+/// Parses `delay[100] q[0]` -- the one built-in statement that takes its
+/// parameter in `[...]` (a duration, not an angle in `(...)`) rather than
+/// the usual gate-call syntax.
+fn parse_delay(rest: &str) -> Result<GateCall, String> {
+    let re = Regex::new(r"^\s*\[([^\]]*)\]\s+(.+)$").unwrap();
+    let caps = re
+        .captures(rest)
+        .ok_or_else(|| format!("invalid delay statement: delay{}", rest))?;
+    let duration = caps[1].trim().to_string();
+    let qubits: Vec<Argument> = caps[2]
+        .split(',')
+        .map(parse_argument)
+        .collect::<Result<_, _>>()?;
+    Ok(GateCall { name: "delay".to_string(), angles: vec![duration], qubits })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_registers() {
+        let nodes = parse("qreg q[2];\ncreg c[2];").unwrap();
+        assert_eq!(
+            nodes,
+            vec![
+                AstNode::QReg("q".to_string(), 2),
+                AstNode::CReg("c".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gate_call() {
+        let nodes = parse("h q[0];\ncx q[0],q[1];\nrz(pi/2) q[0];").unwrap();
+        assert_eq!(
+            nodes,
+            vec![
+                AstNode::ApplyGate(GateCall {
+                    name: "h".to_string(),
+                    angles: vec![],
+                    qubits: vec![Argument::Indexed("q".to_string(), 0)],
+                }),
+                AstNode::ApplyGate(GateCall {
+                    name: "cx".to_string(),
+                    angles: vec![],
+                    qubits: vec![
+                        Argument::Indexed("q".to_string(), 0),
+                        Argument::Indexed("q".to_string(), 1),
+                    ],
+                }),
+                AstNode::ApplyGate(GateCall {
+                    name: "rz".to_string(),
+                    angles: vec!["pi/2".to_string()],
+                    qubits: vec![Argument::Indexed("q".to_string(), 0)],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_delay_takes_its_duration_in_brackets() {
+        let nodes = parse("delay[100] q[0];").unwrap();
+        assert_eq!(
+            nodes,
+            vec![AstNode::ApplyGate(GateCall {
+                name: "delay".to_string(),
+                angles: vec!["100".to_string()],
+                qubits: vec![Argument::Indexed("q".to_string(), 0)],
+            })]
+        );
+    }
+
+    #[test]
+    fn test_parse_measure() {
+        let nodes = parse("measure q[0] -> c[0];").unwrap();
+        assert_eq!(
+            nodes,
+            vec![AstNode::Measure {
+                qubit: Argument::Indexed("q".to_string(), 0),
+                cbit: Argument::Indexed("c".to_string(), 0),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_reset() {
+        let nodes = parse("reset q[0];").unwrap();
+        assert_eq!(nodes, vec![AstNode::Reset(Argument::Indexed("q".to_string(), 0))]);
+    }
+
+    #[test]
+    fn test_parse_barrier() {
+        let nodes = parse("barrier q[0],q[1];").unwrap();
+        assert_eq!(
+            nodes,
+            vec![AstNode::Barrier(vec![
+                Argument::Indexed("q".to_string(), 0),
+                Argument::Indexed("q".to_string(), 1),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_parse_gate_def() {
+        let nodes = parse("gate mycx a,b { cx a,b; }").unwrap();
+        assert_eq!(
+            nodes,
+            vec![AstNode::Gate {
+                name: "mycx".to_string(),
+                params: vec![],
+                qargs: vec!["a".to_string(), "b".to_string()],
+                body: vec![GateCall {
+                    name: "cx".to_string(),
+                    angles: vec![],
+                    qubits: vec![Argument::Register("a".to_string()), Argument::Register("b".to_string())],
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_gate_def_with_params_and_multi_statement_body() {
+        let nodes = parse("gate myrz(theta) q { rz(theta) q; id q; }").unwrap();
+        assert_eq!(
+            nodes,
+            vec![AstNode::Gate {
+                name: "myrz".to_string(),
+                params: vec!["theta".to_string()],
+                qargs: vec!["q".to_string()],
+                body: vec![
+                    GateCall {
+                        name: "rz".to_string(),
+                        angles: vec!["theta".to_string()],
+                        qubits: vec![Argument::Register("q".to_string())],
+                    },
+                    GateCall {
+                        name: "id".to_string(),
+                        angles: vec![],
+                        qubits: vec![Argument::Register("q".to_string())],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_if() {
+        let nodes = parse("if (c==1) x q[0];").unwrap();
+        assert_eq!(
+            nodes,
+            vec![AstNode::If {
+                creg: "c".to_string(),
+                value: 1,
+                call: GateCall {
+                    name: "x".to_string(),
+                    angles: vec![],
+                    qubits: vec![Argument::Indexed("q".to_string(), 0)],
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_mpp() {
+        let nodes = parse(r#"mpp "XZZX" q[0],q[1],q[2],q[3] -> c[0];"#).unwrap();
+        assert_eq!(
+            nodes,
+            vec![AstNode::Mpp {
+                pauli: "XZZX".to_string(),
+                qubits: vec![
+                    Argument::Indexed("q".to_string(), 0),
+                    Argument::Indexed("q".to_string(), 1),
+                    Argument::Indexed("q".to_string(), 2),
+                    Argument::Indexed("q".to_string(), 3),
+                ],
+                cbit: Argument::Indexed("c".to_string(), 0),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_mpp_without_arrow_is_an_error() {
+        assert!(parse(r#"mpp "XZ" q[0],q[1];"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_with_version_v3_recognizes_qubit_and_bit_declarations() {
+        let nodes = parse_with_version("qubit[2] q;\nbit[2] c;", QasmVersion::V3).unwrap();
+        assert_eq!(
+            nodes,
+            vec![
+                AstNode::QReg("q".to_string(), 2),
+                AstNode::CReg("c".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_version_v2_rejects_qubit_and_bit_declarations() {
+        assert!(parse_with_version("qubit[2] q;", QasmVersion::V2).is_err());
+    }
+
+    #[test]
+    fn test_parse_ignores_comments_and_header() {
+        let nodes = parse("OPENQASM 2.0;\ninclude \"qelib1.inc\";\n// a comment\nqreg q[1];").unwrap();
+        assert_eq!(nodes, vec![AstNode::QReg("q".to_string(), 1)]);
+    }
+}
@@ -0,0 +1,173 @@
+use std::str::FromStr;
+
+use crate::angle::Angle;
+use crate::axis::Axis;
+use crate::circuit::Circuit;
+use crate::mod8::Mod8;
+use crate::operator::{Operator, PauliRotation};
+use crate::registers::Registers;
+
+/// Splits a leading `+`/`-` sign off an axis token, defaulting to `+`.
+fn split_sign(token: &str) -> (bool, &str) {
+    if let Some(rest) = token.strip_prefix('-') {
+        (true, rest)
+    } else if let Some(rest) = token.strip_prefix('+') {
+        (false, rest)
+    } else {
+        (false, token)
+    }
+}
+
+/// Parses an angle token of the form `pi/8` or `3pi/8` (optionally
+/// `-`-prefixed), directly giving the `PiOver8` numerator (no halving
+/// convention, unlike `frontend::angle::extract_angle`: this format already
+/// speaks the crate's internal angle representation).
+fn parse_pauli_angle(token: &str) -> Result<Angle, String> {
+    let (neg, rest) = split_sign(token);
+    let coeff_str = rest
+        .strip_suffix("pi/8")
+        .ok_or_else(|| format!("invalid angle token: '{}'", token))?;
+    let coeff: u32 = if coeff_str.is_empty() {
+        1
+    } else {
+        coeff_str
+            .parse()
+            .map_err(|_| format!("invalid angle token: '{}'", token))?
+    };
+    let mut n = coeff % 8;
+    if neg {
+        n = (8 - n) % 8;
+    }
+    Ok(Angle::PiOver8(Mod8::from(n)))
+}
+
+fn check_width(width: &mut Option<usize>, this_width: usize, line_number: usize) -> Result<(), String> {
+    match *width {
+        Some(w) if w != this_width => Err(format!(
+            "line {}: axis width {} does not match the width {} established earlier in the file",
+            line_number, this_width, w
+        )),
+        Some(_) => Ok(()),
+        None => {
+            *width = Some(this_width);
+            Ok(())
+        }
+    }
+}
+
+/// Parses the plain Pauli-rotation text format: one operator per line,
+/// `R <signed-axis> <angle>` for a rotation (e.g. `R +IXZY pi/8`) or `M
+/// <axis>` for a measurement (e.g. `M IZZI`), skipping the QASM frontend
+/// entirely. Blank lines and lines starting with `#` are ignored. The
+/// number of qubits is inferred from the first axis seen and every later
+/// axis must match it. Measurement targets are assigned sequentially in
+/// the order `M` lines appear.
+pub fn parse_pauli_text(text: &str) -> Result<Circuit, String> {
+    let mut width = None;
+    let mut operators = Vec::new();
+    let mut next_target = 0usize;
+
+    for (line_index, raw_line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["R", signed_axis, angle_token] => {
+                let (neg, axis_str) = split_sign(signed_axis);
+                let axis = Axis::from_str(axis_str).map_err(|e| format!("line {}: {}", line_number, e))?;
+                check_width(&mut width, axis.width(), line_number)?;
+                let mut angle =
+                    parse_pauli_angle(angle_token).map_err(|e| format!("line {}: {}", line_number, e))?;
+                if neg {
+                    angle = -angle;
+                }
+                operators.push(Operator::PauliRotation(PauliRotation::new(axis, angle)));
+            }
+            ["M", axis_str] => {
+                let axis = Axis::from_str(axis_str).map_err(|e| format!("line {}: {}", line_number, e))?;
+                check_width(&mut width, axis.width(), line_number)?;
+                operators.push(Operator::Measurement { axis, target: next_target });
+                next_target += 1;
+            }
+            _ => return Err(format!("line {}: unrecognized operator line: '{}'", line_number, line)),
+        }
+    }
+
+    let mut registers = Registers::new();
+    registers.add_qubit_register("q", width.unwrap_or(0));
+    Ok(Circuit::new(registers, operators))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pauli::Pauli;
+
+    #[test]
+    fn test_parse_valid_file() {
+        let text = "R +IXZY pi/8\nM IZZI\n";
+        let circuit = parse_pauli_text(text).unwrap();
+        assert_eq!(circuit.registers.num_qubits(), 4);
+        assert_eq!(
+            circuit.operators,
+            vec![
+                Operator::PauliRotation(PauliRotation::new(
+                    Axis::new(vec![Pauli::I, Pauli::X, Pauli::Z, Pauli::Y]),
+                    Angle::PiOver8(Mod8::One),
+                )),
+                Operator::Measurement {
+                    axis: Axis::new(vec![Pauli::I, Pauli::Z, Pauli::Z, Pauli::I]),
+                    target: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_negative_sign_negates_angle() {
+        let circuit = parse_pauli_text("R -Z pi/8\n").unwrap();
+        assert_eq!(
+            circuit.operators,
+            vec![Operator::PauliRotation(PauliRotation::new(
+                Axis::new(vec![Pauli::Z]),
+                Angle::PiOver8(Mod8::Seven),
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_parse_assigns_sequential_measurement_targets() {
+        let circuit = parse_pauli_text("M Z\nM X\n").unwrap();
+        match circuit.operators.as_slice() {
+            [Operator::Measurement { target: t0, .. }, Operator::Measurement { target: t1, .. }] => {
+                assert_eq!(*t0, 0);
+                assert_eq!(*t1, 1);
+            }
+            other => panic!("unexpected operators: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_mismatched_width() {
+        let result = parse_pauli_text("R +IX pi/8\nM ZZZ\n");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("line 2"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_angle_token() {
+        let result = parse_pauli_text("R +IX banana\n");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("banana"));
+    }
+
+    #[test]
+    fn test_parse_ignores_blank_lines_and_comments() {
+        let circuit = parse_pauli_text("# a comment\n\nM Z\n").unwrap();
+        assert_eq!(circuit.operators.len(), 1);
+    }
+}
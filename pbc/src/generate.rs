@@ -0,0 +1,125 @@
+//! Deterministic synthetic Clifford+T circuit generation, for
+//! benchmarking and fuzzing `spc_translation` against realistic inputs
+//! without needing a real QASM source file on disk.
+
+use crate::frontend::ast::{Argument, GateCall};
+use crate::frontend::gate::translate_gate;
+use crate::operator::Operator;
+use crate::registers::Registers;
+use crate::test_support::Rng;
+
+const SINGLE_QUBIT_CLIFFORDS: &[&str] = &["h", "x", "y", "z", "s", "sdg"];
+
+fn qubit_argument(index: usize) -> Argument {
+    Argument::Indexed("q".to_string(), index)
+}
+
+fn gate_call(name: &str, qubits: &[usize]) -> GateCall {
+    GateCall {
+        name: name.to_string(),
+        angles: Vec::new(),
+        qubits: qubits.iter().map(|&q| qubit_argument(q)).collect(),
+    }
+}
+
+/// One random gate layer: a `t` with probability `t_fraction`, otherwise a
+/// random Clifford gate (a single-qubit Clifford, or `cx` on two distinct
+/// qubits when `num_qubits >= 2`).
+fn random_gate_call(rng: &mut Rng, num_qubits: usize, t_fraction: f64) -> GateCall {
+    const PRECISION: u64 = 1_000_000;
+    if (rng.below(PRECISION) as f64 / PRECISION as f64) < t_fraction {
+        return gate_call("t", &[rng.below(num_qubits as u64) as usize]);
+    }
+
+    if num_qubits >= 2 && rng.below(2) == 0 {
+        let control = rng.below(num_qubits as u64) as usize;
+        let mut target = rng.below(num_qubits as u64) as usize;
+        while target == control {
+            target = rng.below(num_qubits as u64) as usize;
+        }
+        return gate_call("cx", &[control, target]);
+    }
+
+    let name = SINGLE_QUBIT_CLIFFORDS[rng.below(SINGLE_QUBIT_CLIFFORDS.len() as u64) as usize];
+    gate_call(name, &[rng.below(num_qubits as u64) as usize])
+}
+
+/// `depth` random gate calls over `num_qubits` qubits, deterministic for a
+/// given `seed`. Shared by [`random_clifford_t`] and [`random_clifford_t_qasm`]
+/// so that the two always describe the same circuit.
+fn random_gate_calls(num_qubits: usize, depth: usize, t_fraction: f64, seed: u64) -> Vec<GateCall> {
+    assert!(num_qubits > 0, "random_clifford_t requires at least one qubit");
+    let mut rng = Rng::new(seed);
+    (0..depth)
+        .map(|_| random_gate_call(&mut rng, num_qubits, t_fraction))
+        .collect()
+}
+
+/// A random Clifford+T circuit over `num_qubits` qubits with `depth` gate
+/// layers, expressed as the same `Operator` sequence
+/// `frontend::gate::translate_gate` would emit for the equivalent QASM.
+/// Each layer is independently a `t` gate (with probability `t_fraction`)
+/// or a random Clifford gate. Deterministic for a given `seed`.
+pub fn random_clifford_t(num_qubits: usize, depth: usize, t_fraction: f64, seed: u64) -> Vec<Operator> {
+    let mut registers = Registers::new();
+    registers.add_qubit_register("q", num_qubits);
+
+    random_gate_calls(num_qubits, depth, t_fraction, seed)
+        .iter()
+        .flat_map(|call| translate_gate(&registers, call).expect("generated gate calls are always valid"))
+        .collect()
+}
+
+fn render_argument(arg: &Argument) -> String {
+    match arg {
+        Argument::Indexed(name, index) => format!("{}[{}]", name, index),
+        Argument::Register(name) => name.clone(),
+    }
+}
+
+/// The same random circuit as [`random_clifford_t`] with the same
+/// arguments, rendered as OpenQASM 2 source text instead of translated
+/// `Operator`s.
+pub fn random_clifford_t_qasm(num_qubits: usize, depth: usize, t_fraction: f64, seed: u64) -> String {
+    let mut qasm = format!("OPENQASM 2.0;\nqreg q[{}];\n", num_qubits);
+    for call in random_gate_calls(num_qubits, depth, t_fraction, seed) {
+        let qubits = call.qubits.iter().map(render_argument).collect::<Vec<_>>().join(",");
+        qasm.push_str(&format!("{} {};\n", call.name, qubits));
+    }
+    qasm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_clifford_t_is_deterministic_for_a_given_seed() {
+        let first = random_clifford_t(4, 50, 0.2, 7);
+        let second = random_clifford_t(4, 50, 0.2, 7);
+        assert_eq!(first, second);
+        assert!(!first.is_empty());
+    }
+
+    #[test]
+    fn test_random_clifford_t_differs_across_seeds() {
+        let a = random_clifford_t(4, 50, 0.2, 7);
+        let b = random_clifford_t(4, 50, 0.2, 8);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_random_clifford_t_qasm_round_trips_through_the_frontend() {
+        let qasm = random_clifford_t_qasm(3, 30, 0.3, 42);
+        let nodes = crate::frontend::parser::parse(&qasm).unwrap();
+        let circuit = crate::frontend::extract::extract(&nodes).unwrap();
+        assert_eq!(circuit.operators, random_clifford_t(3, 30, 0.3, 42));
+    }
+
+    #[test]
+    fn test_random_clifford_t_qasm_is_deterministic_for_a_given_seed() {
+        let first = random_clifford_t_qasm(4, 50, 0.2, 7);
+        let second = random_clifford_t_qasm(4, 50, 0.2, 7);
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,153 @@
+//! Binary checkpoint format for a [`Circuit`], so a large translation run
+//! can save the result of gate extraction and resume from it later with
+//! different downstream options instead of re-parsing. Only available with
+//! the `serde` feature, which also pulls in `bincode`.
+//!
+//! The on-disk layout is a 4-byte format version, an 8-byte checksum, then
+//! the bincode-encoded payload; [`load`] rejects a version it doesn't
+//! recognize or a payload whose checksum doesn't match before ever handing
+//! back a `Circuit`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::circuit::Circuit;
+use crate::operator::Operator;
+use crate::registers::Registers;
+
+/// Bumped whenever the on-disk payload layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+const HEADER_LEN: usize = 4 + 8;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Payload {
+    registers: Registers,
+    operators: Vec<Operator>,
+}
+
+fn checksum(encoded: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    encoded.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Serializes `circuit` into the binary IR format.
+pub fn save(circuit: &Circuit) -> Result<Vec<u8>, String> {
+    let payload = Payload {
+        registers: circuit.registers.clone(),
+        operators: circuit.operators.clone(),
+    };
+    let encoded = bincode::serde::encode_to_vec(&payload, bincode::config::standard())
+        .map_err(|e| format!("failed to encode IR: {}", e))?;
+
+    let mut out = Vec::with_capacity(HEADER_LEN + encoded.len());
+    out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    out.extend_from_slice(&checksum(&encoded).to_le_bytes());
+    out.extend_from_slice(&encoded);
+    Ok(out)
+}
+
+/// Parses bytes previously produced by [`save`], rejecting a mismatched
+/// format version or a corrupted payload before decoding it.
+pub fn load(bytes: &[u8]) -> Result<Circuit, String> {
+    if bytes.len() < HEADER_LEN {
+        return Err(format!(
+            "IR data is only {} bytes, too short for a {}-byte header",
+            bytes.len(),
+            HEADER_LEN
+        ));
+    }
+
+    let format_version = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+    if format_version != FORMAT_VERSION {
+        return Err(format!(
+            "IR format version {} is not supported by this build (expected {})",
+            format_version, FORMAT_VERSION
+        ));
+    }
+
+    let expected_checksum = u64::from_le_bytes(bytes[4..12].try_into().unwrap());
+    let encoded = &bytes[HEADER_LEN..];
+    let actual_checksum = checksum(encoded);
+    if actual_checksum != expected_checksum {
+        return Err(format!(
+            "IR checksum mismatch: expected {}, got {} (the file may be corrupted)",
+            expected_checksum, actual_checksum
+        ));
+    }
+
+    let (payload, _): (Payload, usize) =
+        bincode::serde::decode_from_slice(encoded, bincode::config::standard())
+            .map_err(|e| format!("failed to decode IR: {}", e))?;
+    Ok(Circuit::new(payload.registers, payload.operators))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Angle;
+    use crate::axis::Axis;
+    use crate::mod8::Mod8;
+    use crate::operator::PauliRotation;
+    use crate::pauli::Pauli;
+    use crate::spc_compact::spc_compact_translation;
+
+    fn fixture_circuit() -> Circuit {
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        registers.add_cbit_register("c", 2);
+        let operators = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::Two),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_paulis(2, &[(0, Pauli::Z), (1, Pauli::X)]),
+                Angle::PiOver8(Mod8::One),
+            )),
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(2, 0, Pauli::Z),
+                target: 0,
+            },
+        ];
+        Circuit::new(registers, operators)
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let circuit = fixture_circuit();
+        let bytes = save(&circuit).unwrap();
+        let loaded = load(&bytes).unwrap();
+        assert_eq!(loaded, circuit);
+    }
+
+    #[test]
+    fn test_rejects_mismatched_format_version() {
+        let mut bytes = save(&fixture_circuit()).unwrap();
+        bytes[0..4].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let err = load(&bytes).unwrap_err();
+        assert!(err.contains("format version"));
+    }
+
+    #[test]
+    fn test_rejects_corrupted_payload() {
+        let mut bytes = save(&fixture_circuit()).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        let err = load(&bytes).unwrap_err();
+        assert!(err.contains("checksum"));
+    }
+
+    #[test]
+    fn test_loaded_ir_matches_single_pass_compact_translation() {
+        let circuit = fixture_circuit();
+        let direct = spc_compact_translation(&circuit.operators);
+
+        let bytes = save(&circuit).unwrap();
+        let loaded = load(&bytes).unwrap();
+        let from_ir = spc_compact_translation(&loaded.operators);
+
+        assert_eq!(direct, from_ir);
+    }
+}
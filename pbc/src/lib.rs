@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod alloc_count;
+pub mod analysis;
+pub mod angle;
+pub mod annotated;
+pub mod axis;
+pub mod circuit;
+pub mod frontend;
+pub mod generate;
+#[cfg(feature = "serde")]
+pub mod ir;
+pub mod mod8;
+pub mod operator;
+pub mod parity;
+pub mod pauli;
+pub mod registers;
+pub mod sign;
+pub mod signed_axis;
+pub mod spc;
+pub mod spc_compact;
+pub mod test_support;
+
+pub use analysis::anticommuting_measurement_pairs;
+pub use analysis::anticommuting_overlap_count;
+pub use analysis::commutation_matrix;
+pub use analysis::identity_measurements;
+pub use analysis::longest_anticommuting_chain;
+pub use analysis::magic_state_axes;
+pub use analysis::overlapping_support_count;
+pub use analysis::measurement_layers;
+pub use analysis::peak_magic_parallelism;
+pub use analysis::phase_frame;
+pub use analysis::t_count_per_qubit;
+pub use angle::Angle;
+pub use annotated::{annotate_operators, AnnotatedOperator};
+pub use axis::Axis;
+pub use circuit::Circuit;
+pub use mod8::Mod8;
+pub use frontend::{extract, extract_with_options, extract_with_warnings, ExtractOptions, QasmVersion};
+pub use frontend::parser::{parse, parse_with_version};
+pub use frontend::parse_pauli_text;
+pub use operator::{Operator, PauliRotation};
+pub use parity::measurement_to_parity_circuit;
+pub use pauli::Pauli;
+pub use registers::Registers;
+pub use sign::Sign;
+pub use signed_axis::SignedAxis;
+pub use spc::{
+    logical_frame_sign_changes, peephole_fuse_single_qubit, spc_translation, spc_translation_checked,
+    spc_translation_with_frame_cleanup, spc_translation_with_frame_trace, LogicalFrameSign,
+};
@@ -0,0 +1,152 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// An integer modulo 8, used as the numerator of a `pi/8` rotation angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mod8 {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+}
+
+impl Mod8 {
+    pub fn from(n: u32) -> Mod8 {
+        match n % 8 {
+            0 => Mod8::Zero,
+            1 => Mod8::One,
+            2 => Mod8::Two,
+            3 => Mod8::Three,
+            4 => Mod8::Four,
+            5 => Mod8::Five,
+            6 => Mod8::Six,
+            7 => Mod8::Seven,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn to_u32(&self) -> u32 {
+        match self {
+            Mod8::Zero => 0,
+            Mod8::One => 1,
+            Mod8::Two => 2,
+            Mod8::Three => 3,
+            Mod8::Four => 4,
+            Mod8::Five => 5,
+            Mod8::Six => 6,
+            Mod8::Seven => 7,
+        }
+    }
+
+    /// The representative of this value with the smallest absolute value,
+    /// in `(-4, 4]`: `Five`, `Six`, and `Seven` (numerators 5-7) wrap around
+    /// to `-3`, `-2`, and `-1` rather than staying positive, since e.g.
+    /// `5 * pi/8` and `-3 * pi/8` are the same rotation up to a full turn.
+    /// Used to display `PiOver8` angles with their shortest numerator.
+    pub fn canonical_numerator(&self) -> i32 {
+        let n = self.to_u32() as i32;
+        if n <= 4 {
+            n
+        } else {
+            n - 8
+        }
+    }
+}
+
+impl Neg for Mod8 {
+    type Output = Mod8;
+
+    fn neg(self) -> Mod8 {
+        Mod8::from((8 - self.to_u32()) % 8)
+    }
+}
+
+impl Add for Mod8 {
+    type Output = Mod8;
+
+    fn add(self, rhs: Mod8) -> Mod8 {
+        Mod8::from(self.to_u32() + rhs.to_u32())
+    }
+}
+
+impl Sub for Mod8 {
+    type Output = Mod8;
+
+    fn sub(self, rhs: Mod8) -> Mod8 {
+        Mod8::from(self.to_u32() + 8 - rhs.to_u32())
+    }
+}
+
+impl AddAssign for Mod8 {
+    fn add_assign(&mut self, rhs: Mod8) {
+        *self = *self + rhs;
+    }
+}
+
+/// Scalar multiplication by a `u32`, e.g. `Mod8::Three * 5 == Mod8::from(15)`.
+impl Mul<u32> for Mod8 {
+    type Output = Mod8;
+
+    fn mul(self, rhs: u32) -> Mod8 {
+        Mod8::from(self.to_u32() * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Mod8::from(0), Mod8::Zero);
+        assert_eq!(Mod8::from(7), Mod8::Seven);
+        assert_eq!(Mod8::from(8), Mod8::Zero);
+        assert_eq!(Mod8::from(11), Mod8::Three);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Mod8::Zero, Mod8::Zero);
+        assert_eq!(-Mod8::One, Mod8::Seven);
+        assert_eq!(-Mod8::Four, Mod8::Four);
+    }
+
+    #[test]
+    fn test_add_and_sub_match_a_reference_mod_8_computation_for_every_pair() {
+        for a in 0..8 {
+            for b in 0..8 {
+                assert_eq!(Mod8::from(a) + Mod8::from(b), Mod8::from((a + b) % 8), "a={} b={}", a, b);
+                assert_eq!(Mod8::from(a) - Mod8::from(b), Mod8::from((a + 8 - b) % 8), "a={} b={}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut m = Mod8::Six;
+        m += Mod8::Four;
+        assert_eq!(m, Mod8::Two);
+    }
+
+    #[test]
+    fn test_mul_by_scalar() {
+        assert_eq!(Mod8::Three * 5, Mod8::from(15));
+        assert_eq!(Mod8::Two * 4, Mod8::Zero);
+    }
+
+    #[test]
+    fn test_canonical_numerator_covers_every_value() {
+        assert_eq!(Mod8::Zero.canonical_numerator(), 0);
+        assert_eq!(Mod8::One.canonical_numerator(), 1);
+        assert_eq!(Mod8::Two.canonical_numerator(), 2);
+        assert_eq!(Mod8::Three.canonical_numerator(), 3);
+        assert_eq!(Mod8::Four.canonical_numerator(), 4);
+        assert_eq!(Mod8::Five.canonical_numerator(), -3);
+        assert_eq!(Mod8::Six.canonical_numerator(), -2);
+        assert_eq!(Mod8::Seven.canonical_numerator(), -1);
+    }
+}
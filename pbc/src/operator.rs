@@ -0,0 +1,678 @@
+use std::fmt;
+use std::rc::Rc;
+
+use crate::angle::Angle;
+use crate::axis::Axis;
+use crate::mod8::Mod8;
+use crate::pauli::Pauli;
+
+/// A Pauli rotation `exp(-i * angle * axis)`.
+///
+/// `axis` is kept behind an `Rc` so that a rotation absorbed into an SPC
+/// frame (see `spc::spc_translation`) and the output it was split off from
+/// can share the same underlying `Axis` data instead of each holding their
+/// own copy.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauliRotation {
+    pub axis: Rc<Axis>,
+    pub angle: Angle,
+}
+
+impl PauliRotation {
+    pub fn new(axis: impl Into<Rc<Axis>>, angle: Angle) -> PauliRotation {
+        PauliRotation { axis: axis.into(), angle }
+    }
+
+    /// Whether this rotation is a Clifford operation, i.e. its angle is a
+    /// multiple of `pi/4`.
+    pub fn is_clifford(&self) -> bool {
+        match self.angle {
+            Angle::PiOver8(m) => m.to_u32() % 2 == 0,
+            Angle::Arbitrary(_) => false,
+        }
+    }
+
+    /// The inverse rotation: same axis, negated angle, since
+    /// `exp(-i * angle * axis)`'s inverse is `exp(i * angle * axis)`.
+    pub fn dagger(&self) -> PauliRotation {
+        PauliRotation::new(self.axis.clone(), -self.angle)
+    }
+
+    /// Splits a non-Clifford `PiOver8` rotation into a Clifford part to
+    /// fold into an SPC frame and a `pi/8` remainder to emit, such that
+    /// applying the Clifford part followed by the remainder is equivalent
+    /// to the original rotation. `One` and `Seven` have no Clifford part
+    /// to extract -- they're already pure magic rotations -- so the first
+    /// element is `None` and the second echoes `self`'s angle.
+    ///
+    /// Panics if `self`'s angle isn't an odd-numerator `PiOver8`: an
+    /// `Arbitrary` angle has no such split, and an even numerator is
+    /// already Clifford with nothing to extract.
+    pub fn split_non_clifford(&self) -> (Option<PauliRotation>, PauliRotation) {
+        let n = match self.angle {
+            Angle::PiOver8(n) if n.to_u32() % 2 == 1 => n,
+            _ => panic!("split_non_clifford: angle must be an odd-numerator PiOver8, got {}", self.angle),
+        };
+        let (clifford, remainder) = match n {
+            Mod8::One | Mod8::Seven => (None, n),
+            Mod8::Three => (Some(Mod8::Four), Mod8::Seven),
+            Mod8::Five => (Some(Mod8::Four), Mod8::One),
+            _ => unreachable!("odd Mod8 numerators are exactly One, Three, Five, Seven"),
+        };
+        let clifford = clifford.map(|c| PauliRotation::new(Rc::clone(&self.axis), Angle::PiOver8(c)));
+        let remainder = PauliRotation::new(Rc::clone(&self.axis), Angle::PiOver8(remainder));
+        (clifford, remainder)
+    }
+}
+
+/// Displays as `<axis> <angle>`, e.g. `XZ PiOver8(6)`. The alternate form
+/// (`{:#}`) passes through to `Angle`'s alternate form, showing the
+/// canonical numerator instead, e.g. `XZ -PiOver8(2)`.
+impl fmt::Display for PauliRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{} {:#}", self.axis, self.angle)
+        } else {
+            write!(f, "{} {}", self.axis, self.angle)
+        }
+    }
+}
+
+/// A single step of a Pauli-based computation: a rotation about an axis, a
+/// measurement of an axis reported into a classical target bit, or a reset
+/// of a qubit to a fresh, known state.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operator {
+    PauliRotation(PauliRotation),
+    Measurement { axis: Axis, target: usize },
+    /// Reinitializes `qubit` to a known state, discarding whatever it held
+    /// before. Unlike a rotation or measurement, a reset has no axis: it
+    /// doesn't act on the circuit's existing Pauli frame, it replaces the
+    /// qubit the frame was describing.
+    Reset { qubit: usize },
+    /// A scheduling fence over the listed qubits: carries no quantum
+    /// operation of its own, so it passes through `spc_translation`
+    /// untouched, but it must never be reordered past -- see
+    /// `can_swap_with`.
+    Barrier(Vec<usize>),
+    /// `inner`, applied only when the classical bits `cbits` (in order, as
+    /// a binary number) equal `value` -- e.g. `if (c==1) x q[0];` -- for
+    /// modeling feed-forward Pauli corrections. `spc_translation` still
+    /// conjugates `inner`'s axis through the Clifford frame, but never
+    /// absorbs a conditional into the frame itself, since whether it fires
+    /// isn't known until runtime.
+    Conditional { cbits: Vec<usize>, value: u64, inner: Box<Operator> },
+}
+
+impl Operator {
+    /// The axis this operator acts on, or `None` for a `Reset` or `Barrier`
+    /// (neither has an axis to speak of).
+    pub fn axis(&self) -> Option<&Axis> {
+        match self {
+            Operator::PauliRotation(r) => Some(r.axis.as_ref()),
+            Operator::Measurement { axis, .. } => Some(axis),
+            Operator::Reset { .. } | Operator::Barrier(_) => None,
+            Operator::Conditional { inner, .. } => inner.axis(),
+        }
+    }
+
+    pub fn is_clifford(&self) -> bool {
+        match self {
+            Operator::PauliRotation(r) => r.is_clifford(),
+            Operator::Measurement { .. } | Operator::Reset { .. } | Operator::Barrier(_) => true,
+            Operator::Conditional { inner, .. } => inner.is_clifford(),
+        }
+    }
+
+    pub fn is_measurement(&self) -> bool {
+        matches!(self, Operator::Measurement { .. })
+    }
+
+    /// The inverse of this operator: a rotation daggers to its negated-angle
+    /// counterpart; a measurement, reset or barrier has no well-defined
+    /// inverse (a measurement can't be undone, a reset discards whatever
+    /// state came before it, and a barrier carries no operation to invert)
+    /// so they pass through unchanged. Building the actual inverse
+    /// *circuit* also means reversing operator order, which this doesn't
+    /// do on its own -- see the CLI's `--invert` flag.
+    pub fn dagger(&self) -> Operator {
+        match self {
+            Operator::PauliRotation(r) => Operator::PauliRotation(r.dagger()),
+            Operator::Measurement { .. } | Operator::Reset { .. } | Operator::Barrier(_) => self.clone(),
+            Operator::Conditional { cbits, value, inner } => Operator::Conditional {
+                cbits: cbits.clone(),
+                value: *value,
+                inner: Box::new(inner.dagger()),
+            },
+        }
+    }
+
+    /// Whether `self` and `other` can be swapped in a circuit without
+    /// changing its semantics. For two rotations or measurements, this is
+    /// exactly axis commutation. A reset can be swapped with an operator
+    /// that doesn't act on its qubit, but not with one that does, since
+    /// their relative order then determines whether the action happens
+    /// before or after the qubit is reinitialized; two resets always
+    /// commute, whether or not they target the same qubit. A barrier never
+    /// swaps with anything -- including another barrier -- since it's a
+    /// scheduling fence: the entire point is that nothing crosses it. A
+    /// conditional never swaps with anything either: its classical
+    /// dependency (on whatever measurement set the bits it reads) isn't
+    /// visible to axis commutation, so reordering it can't be shown safe.
+    pub fn can_swap_with(&self, other: &Operator) -> bool {
+        match (self, other) {
+            (Operator::Barrier(_), _) | (_, Operator::Barrier(_)) => false,
+            (Operator::Conditional { .. }, _) | (_, Operator::Conditional { .. }) => false,
+            (Operator::Reset { .. }, Operator::Reset { .. }) => true,
+            (Operator::Reset { qubit }, _) => {
+                other.axis().is_some_and(|axis| axis.get(*qubit) == Pauli::I)
+            }
+            (_, Operator::Reset { qubit }) => {
+                self.axis().is_some_and(|axis| axis.get(*qubit) == Pauli::I)
+            }
+            _ => self.axis().unwrap().commutes_with(other.axis().unwrap()),
+        }
+    }
+
+    /// Reindexes this operator for an edited register layout. `old_to_new[i]`
+    /// gives the new index of what used to be qubit `i`, or `None` if that
+    /// qubit was removed; for a rotation or measurement, a removed qubit
+    /// must already carry identity, or this errors (removing a qubit the
+    /// axis still acts on would silently change what the operator means).
+    /// For a reset, the reset qubit itself must not have been removed.
+    pub fn relabel(&self, old_to_new: &[Option<usize>], new_width: usize) -> Result<Operator, String> {
+        match self {
+            Operator::PauliRotation(r) => {
+                let axis = relabel_axis(r.axis.as_ref(), old_to_new, new_width)?;
+                Ok(Operator::PauliRotation(PauliRotation::new(axis, r.angle)))
+            }
+            Operator::Measurement { axis, target } => {
+                let axis = relabel_axis(axis, old_to_new, new_width)?;
+                Ok(Operator::Measurement { axis, target: *target })
+            }
+            Operator::Reset { qubit } => {
+                let new_qubit = old_to_new
+                    .get(*qubit)
+                    .copied()
+                    .flatten()
+                    .ok_or_else(|| format!("relabel: reset qubit {} was removed", qubit))?;
+                Ok(Operator::Reset { qubit: new_qubit })
+            }
+            Operator::Barrier(qubits) => {
+                let new_qubits = qubits
+                    .iter()
+                    .map(|&qubit| {
+                        old_to_new
+                            .get(qubit)
+                            .copied()
+                            .flatten()
+                            .ok_or_else(|| format!("relabel: barrier qubit {} was removed", qubit))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Operator::Barrier(new_qubits))
+            }
+            Operator::Conditional { cbits, value, inner } => Ok(Operator::Conditional {
+                cbits: cbits.clone(),
+                value: *value,
+                inner: Box::new(inner.relabel(old_to_new, new_width)?),
+            }),
+        }
+    }
+
+    /// Permutes this operator's qubit indices according to `permutation`
+    /// (`permutation[i]` is the new index of old qubit `i`), e.g. to apply a
+    /// hardware qubit remapping before translation. `permutation` must be a
+    /// bijection on `0..permutation.len()`; the caller is expected to have
+    /// validated that (see `relabel`, which this delegates to).
+    pub fn map_axis(&self, permutation: &[usize]) -> Operator {
+        let old_to_new: Vec<Option<usize>> = permutation.iter().map(|&p| Some(p)).collect();
+        self.relabel(&old_to_new, permutation.len())
+            .expect("map_axis: permutation must be a bijection, as validated by the caller")
+    }
+}
+
+/// Displays each variant's own shorthand (`rotation`, `measure`, `reset`,
+/// `barrier`, `if`). The alternate form (`{:#}`) passes through to
+/// `PauliRotation`/`Angle`'s alternate form for a `PauliRotation`, showing
+/// its angle's canonical numerator, and a `Conditional` passes it on to its
+/// inner operator; the other variants have no angle, so their alternate
+/// form is the same as their normal one.
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::PauliRotation(r) if f.alternate() => write!(f, "rotation {:#}", r),
+            Operator::PauliRotation(r) => write!(f, "rotation {}", r),
+            Operator::Measurement { axis, target } => write!(f, "measure {} -> c[{}]", axis, target),
+            Operator::Reset { qubit } => write!(f, "reset q[{}]", qubit),
+            Operator::Barrier(qubits) => {
+                write!(f, "barrier")?;
+                for (i, qubit) in qubits.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " q[{}]", qubit)?;
+                }
+                Ok(())
+            }
+            Operator::Conditional { cbits, value, inner } => {
+                write!(f, "if (")?;
+                for (i, cbit) in cbits.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "c[{}]", cbit)?;
+                }
+                if f.alternate() {
+                    write!(f, "=={}) {:#}", value, inner)
+                } else {
+                    write!(f, "=={}) {}", value, inner)
+                }
+            }
+        }
+    }
+}
+
+fn relabel_axis(axis: &Axis, old_to_new: &[Option<usize>], new_width: usize) -> Result<Axis, String> {
+    if old_to_new.len() != axis.width() {
+        return Err(format!(
+            "relabel: old_to_new has {} entries but the axis has width {}",
+            old_to_new.len(),
+            axis.width()
+        ));
+    }
+
+    let mut paulis = vec![Pauli::I; new_width];
+    for (old_index, pauli) in axis.as_slice().iter().enumerate() {
+        match old_to_new[old_index] {
+            Some(new_index) => {
+                let slot = paulis.get_mut(new_index).ok_or_else(|| {
+                    format!(
+                        "relabel: new index {} is out of bounds for width {}",
+                        new_index, new_width
+                    )
+                })?;
+                *slot = *pauli;
+            }
+            None if *pauli != Pauli::I => {
+                return Err(format!(
+                    "relabel: qubit {} was removed but its axis is {:?}, not identity",
+                    old_index, pauli
+                ));
+            }
+            None => {}
+        }
+    }
+    Ok(Axis::new(paulis))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod8::Mod8;
+    use crate::pauli::Pauli;
+
+    #[test]
+    fn test_is_clifford() {
+        let t = PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        );
+        assert!(!t.is_clifford());
+
+        let s = PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Two),
+        );
+        assert!(s.is_clifford());
+    }
+
+    #[test]
+    fn test_split_non_clifford_three_yields_a_four_clifford_and_a_seven_remainder() {
+        let axis = Axis::new_with_pauli(1, 0, Pauli::Z);
+        let rotation = PauliRotation::new(axis.clone(), Angle::PiOver8(Mod8::Three));
+
+        let (clifford, remainder) = rotation.split_non_clifford();
+
+        assert_eq!(clifford, Some(PauliRotation::new(axis.clone(), Angle::PiOver8(Mod8::Four))));
+        assert_eq!(remainder, PauliRotation::new(axis, Angle::PiOver8(Mod8::Seven)));
+    }
+
+    #[test]
+    fn test_split_non_clifford_five_yields_a_four_clifford_and_a_one_remainder() {
+        let axis = Axis::new_with_pauli(1, 0, Pauli::X);
+        let rotation = PauliRotation::new(axis.clone(), Angle::PiOver8(Mod8::Five));
+
+        let (clifford, remainder) = rotation.split_non_clifford();
+
+        assert_eq!(clifford, Some(PauliRotation::new(axis.clone(), Angle::PiOver8(Mod8::Four))));
+        assert_eq!(remainder, PauliRotation::new(axis, Angle::PiOver8(Mod8::One)));
+    }
+
+    #[test]
+    fn test_split_non_clifford_one_has_no_clifford_part() {
+        let axis = Axis::new_with_pauli(1, 0, Pauli::Y);
+        let rotation = PauliRotation::new(axis.clone(), Angle::PiOver8(Mod8::One));
+
+        let (clifford, remainder) = rotation.split_non_clifford();
+
+        assert_eq!(clifford, None);
+        assert_eq!(remainder, rotation);
+    }
+
+    #[test]
+    #[should_panic(expected = "split_non_clifford")]
+    fn test_split_non_clifford_panics_on_a_clifford_angle() {
+        PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::Z), Angle::PiOver8(Mod8::Two)).split_non_clifford();
+    }
+
+    #[test]
+    fn test_relabel_shifts_axes_for_an_inserted_qubit() {
+        // Inserting a new qubit at position 1 pushes the old qubit 1 out to
+        // position 2, leaving qubit 0 where it was.
+        let op = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_paulis(2, &[(0, Pauli::X), (1, Pauli::Y)]),
+            Angle::PiOver8(Mod8::One),
+        ));
+        let relabeled = op.relabel(&[Some(0), Some(2)], 3).unwrap();
+        assert_eq!(
+            relabeled.axis(),
+            Some(&Axis::new_with_paulis(3, &[(0, Pauli::X), (2, Pauli::Y)]))
+        );
+    }
+
+    #[test]
+    fn test_relabel_preserves_target_and_angle() {
+        let op = Operator::Measurement {
+            axis: Axis::new_with_pauli(2, 1, Pauli::Z),
+            target: 5,
+        };
+        let relabeled = op.relabel(&[Some(0), Some(2)], 3).unwrap();
+        assert_eq!(
+            relabeled,
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(3, 2, Pauli::Z),
+                target: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_relabel_errors_when_a_removed_qubit_is_not_identity() {
+        let op = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 1, Pauli::X),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        assert!(op.relabel(&[Some(0), None], 1).is_err());
+    }
+
+    #[test]
+    fn test_relabel_allows_removing_an_identity_qubit() {
+        let op = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 0, Pauli::X),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        let relabeled = op.relabel(&[Some(0), None], 1).unwrap();
+        assert_eq!(relabeled.axis(), Some(&Axis::new_with_pauli(1, 0, Pauli::X)));
+    }
+
+    #[test]
+    fn test_relabel_errors_on_mismatched_old_to_new_length() {
+        let op = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 0, Pauli::X),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        assert!(op.relabel(&[Some(0)], 2).is_err());
+    }
+
+    #[test]
+    fn test_relabel_shifts_a_reset_qubit() {
+        let op = Operator::Reset { qubit: 1 };
+        let relabeled = op.relabel(&[Some(0), Some(2)], 3).unwrap();
+        assert_eq!(relabeled, Operator::Reset { qubit: 2 });
+    }
+
+    #[test]
+    fn test_relabel_errors_when_the_reset_qubit_is_removed() {
+        let op = Operator::Reset { qubit: 1 };
+        assert!(op.relabel(&[Some(0), None], 1).is_err());
+    }
+
+    #[test]
+    fn test_reset_has_no_axis() {
+        assert_eq!(Operator::Reset { qubit: 0 }.axis(), None);
+    }
+
+    #[test]
+    fn test_map_axis_reverses_a_two_qubit_order() {
+        let op = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_paulis(2, &[(0, Pauli::X), (1, Pauli::Z)]),
+            Angle::PiOver8(Mod8::One),
+        ));
+        let mapped = op.map_axis(&[1, 0]);
+        assert_eq!(
+            mapped,
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_paulis(2, &[(0, Pauli::Z), (1, Pauli::X)]),
+                Angle::PiOver8(Mod8::One),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_map_axis_remaps_a_reset_qubit() {
+        let op = Operator::Reset { qubit: 0 };
+        assert_eq!(op.map_axis(&[1, 0]), Operator::Reset { qubit: 1 });
+    }
+
+    #[test]
+    fn test_can_swap_with_commuting_rotations() {
+        let a = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        ));
+        let b = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Two),
+        ));
+        assert!(a.can_swap_with(&b));
+    }
+
+    #[test]
+    fn test_can_swap_with_anticommuting_rotation_and_measurement() {
+        let rotation = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::X),
+            Angle::PiOver8(Mod8::One),
+        ));
+        let measurement = Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::Z), target: 0 };
+        assert!(!rotation.can_swap_with(&measurement));
+    }
+
+    #[test]
+    fn test_can_swap_with_reset_and_disjoint_operator() {
+        let reset = Operator::Reset { qubit: 0 };
+        let other = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 1, Pauli::X),
+            Angle::PiOver8(Mod8::One),
+        ));
+        assert!(reset.can_swap_with(&other));
+        assert!(other.can_swap_with(&reset));
+    }
+
+    #[test]
+    fn test_can_swap_with_reset_and_same_qubit_operator() {
+        let reset = Operator::Reset { qubit: 0 };
+        let other = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::X),
+            Angle::PiOver8(Mod8::One),
+        ));
+        assert!(!reset.can_swap_with(&other));
+    }
+
+    #[test]
+    fn test_display_and_alternate_display_for_pauli_rotation() {
+        let rotation = PauliRotation::new(Axis::new_with_pauli(2, 0, Pauli::X), Angle::PiOver8(Mod8::Six));
+        assert_eq!(rotation.to_string(), "XI PiOver8(6)");
+        assert_eq!(format!("{:#}", rotation), "XI -PiOver8(2)");
+    }
+
+    #[test]
+    fn test_display_and_alternate_display_for_operator() {
+        let rotation = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Five),
+        ));
+        assert_eq!(rotation.to_string(), "rotation Z PiOver8(5)");
+        assert_eq!(format!("{:#}", rotation), "rotation Z -PiOver8(3)");
+
+        let measurement = Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::Z), target: 3 };
+        assert_eq!(measurement.to_string(), "measure Z -> c[3]");
+        assert_eq!(format!("{:#}", measurement), measurement.to_string());
+
+        let reset = Operator::Reset { qubit: 2 };
+        assert_eq!(reset.to_string(), "reset q[2]");
+        assert_eq!(format!("{:#}", reset), reset.to_string());
+    }
+
+    #[test]
+    fn test_dagger_negates_a_rotations_angle() {
+        let rotation = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        ));
+        assert_eq!(
+            rotation.dagger(),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::Seven),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_dagger_leaves_measurements_and_resets_unchanged() {
+        let measurement = Operator::Measurement { axis: Axis::new_with_pauli(1, 0, Pauli::X), target: 0 };
+        assert_eq!(measurement.dagger(), measurement);
+
+        let reset = Operator::Reset { qubit: 0 };
+        assert_eq!(reset.dagger(), reset);
+    }
+
+    #[test]
+    fn test_can_swap_with_two_resets() {
+        assert!(Operator::Reset { qubit: 0 }.can_swap_with(&Operator::Reset { qubit: 0 }));
+        assert!(Operator::Reset { qubit: 0 }.can_swap_with(&Operator::Reset { qubit: 1 }));
+    }
+
+    #[test]
+    fn test_barrier_has_no_axis() {
+        assert_eq!(Operator::Barrier(vec![0, 1]).axis(), None);
+    }
+
+    #[test]
+    fn test_barrier_never_swaps_with_anything_including_another_barrier() {
+        let barrier = Operator::Barrier(vec![0]);
+        let disjoint = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 1, Pauli::X),
+            Angle::PiOver8(Mod8::One),
+        ));
+        assert!(!barrier.can_swap_with(&disjoint));
+        assert!(!disjoint.can_swap_with(&barrier));
+        assert!(!barrier.can_swap_with(&Operator::Barrier(vec![1])));
+    }
+
+    #[test]
+    fn test_relabel_shifts_barrier_qubits() {
+        let op = Operator::Barrier(vec![0, 1]);
+        let relabeled = op.relabel(&[Some(2), Some(0)], 3).unwrap();
+        assert_eq!(relabeled, Operator::Barrier(vec![2, 0]));
+    }
+
+    #[test]
+    fn test_relabel_errors_when_a_barrier_qubit_is_removed() {
+        let op = Operator::Barrier(vec![0, 1]);
+        assert!(op.relabel(&[Some(0), None], 1).is_err());
+    }
+
+    #[test]
+    fn test_dagger_leaves_a_barrier_unchanged() {
+        let barrier = Operator::Barrier(vec![0, 1]);
+        assert_eq!(barrier.dagger(), barrier);
+    }
+
+    #[test]
+    fn test_display_for_barrier() {
+        let barrier = Operator::Barrier(vec![0, 2]);
+        assert_eq!(barrier.to_string(), "barrier q[0], q[2]");
+        assert_eq!(format!("{:#}", barrier), barrier.to_string());
+    }
+
+    fn conditional_x() -> Operator {
+        Operator::Conditional {
+            cbits: vec![0],
+            value: 1,
+            inner: Box::new(Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            ))),
+        }
+    }
+
+    #[test]
+    fn test_conditional_axis_and_is_clifford_delegate_to_the_inner_operator() {
+        let op = conditional_x();
+        assert_eq!(op.axis(), Some(&Axis::new_with_pauli(1, 0, Pauli::X)));
+        assert!(op.is_clifford());
+    }
+
+    #[test]
+    fn test_conditional_never_swaps_with_anything() {
+        let op = conditional_x();
+        let disjoint = Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(2, 1, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        ));
+        assert!(!op.can_swap_with(&disjoint));
+        assert!(!disjoint.can_swap_with(&op));
+        assert!(!op.can_swap_with(&op));
+    }
+
+    #[test]
+    fn test_conditional_relabel_remaps_the_inner_operators_qubits_but_not_its_cbits() {
+        let op = conditional_x();
+        let relabeled = op.relabel(&[Some(1)], 2).unwrap();
+        assert_eq!(
+            relabeled,
+            Operator::Conditional {
+                cbits: vec![0],
+                value: 1,
+                inner: Box::new(Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(2, 1, Pauli::X),
+                    Angle::PiOver8(Mod8::Four),
+                ))),
+            }
+        );
+    }
+
+    #[test]
+    fn test_conditional_dagger_daggers_the_inner_operator() {
+        let op = conditional_x();
+        assert_eq!(
+            op.dagger(),
+            Operator::Conditional {
+                cbits: vec![0],
+                value: 1,
+                inner: Box::new(Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(1, 0, Pauli::X),
+                    Angle::PiOver8(Mod8::Four),
+                ))),
+            }
+        );
+    }
+
+    #[test]
+    fn test_display_for_conditional() {
+        assert_eq!(conditional_x().to_string(), "if (c[0]==1) rotation X PiOver8(4)");
+    }
+}
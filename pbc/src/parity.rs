@@ -0,0 +1,108 @@
+use crate::angle::Angle;
+use crate::axis::Axis;
+use crate::mod8::Mod8;
+use crate::operator::{Operator, PauliRotation};
+use crate::pauli::Pauli;
+
+/// Appends the rotations equivalent to a CNOT gate with `control`/`target`
+/// qubit indices in a `width`-qubit register: the same `Z(pi/4), X(pi/4),
+/// ZX(-pi/4)` decomposition `translate_gate` uses for QASM's `cx`.
+fn push_cnot(out: &mut Vec<Operator>, width: usize, control: usize, target: usize) {
+    out.push(Operator::PauliRotation(PauliRotation::new(
+        Axis::new_with_pauli(width, control, Pauli::Z),
+        Angle::PiOver8(Mod8::Two),
+    )));
+    out.push(Operator::PauliRotation(PauliRotation::new(
+        Axis::new_with_pauli(width, target, Pauli::X),
+        Angle::PiOver8(Mod8::Two),
+    )));
+    out.push(Operator::PauliRotation(PauliRotation::new(
+        Axis::new_with_paulis(width, &[(control, Pauli::Z), (target, Pauli::X)]),
+        Angle::PiOver8(Mod8::Six),
+    )));
+}
+
+/// Decomposes a measurement of `axis` -- a Z-type stabilizer like `IZZI`,
+/// i.e. an axis whose non-identity entries are all `Pauli::Z` -- into the
+/// ancilla-based circuit some backends use in place of a native multi-qubit
+/// measurement: CNOT every qubit `axis` acts on into a fresh ancilla
+/// (appended at index `axis.width()`, one past `axis`'s own qubits), then
+/// measure the ancilla. A CNOT from a qubit already in the Z basis to the
+/// ancilla doesn't disturb that qubit's Z eigenstate, so unlike a general
+/// stabilizer measurement this needs no uncompute step afterward -- the
+/// ancilla's readout is exactly the parity of `axis`.
+///
+/// `axis` entries other than `Pauli::I`/`Pauli::Z` aren't supported: a
+/// general Pauli string would first need its `X`/`Y` qubits rotated into the
+/// Z basis (and back out afterward), which is outside this function's
+/// scope -- callers with a mixed-basis axis should do that themselves before
+/// calling this. The returned measurement's target classical bit is always
+/// `0`; callers that need a different target should remap it.
+pub fn measurement_to_parity_circuit(axis: &Axis) -> Vec<Operator> {
+    let width = axis.width();
+    let ancilla = width;
+    let total_width = width + 1;
+
+    let mut out = Vec::new();
+    for (qubit, pauli) in axis.as_slice().iter().enumerate() {
+        match pauli {
+            Pauli::I => {}
+            Pauli::Z => push_cnot(&mut out, total_width, qubit, ancilla),
+            other => panic!(
+                "measurement_to_parity_circuit: qubit {} has unsupported Pauli {:?}; only Z-type axes are supported",
+                qubit, other
+            ),
+        }
+    }
+    out.push(Operator::Measurement {
+        axis: Axis::new_with_pauli(total_width, ancilla, Pauli::Z),
+        target: 0,
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::Circuit;
+    use crate::registers::Registers;
+
+    #[test]
+    fn test_measurement_to_parity_circuit_decomposes_a_two_qubit_zz_measurement() {
+        let axis = Axis::new(vec![Pauli::Z, Pauli::Z]);
+        let ops = measurement_to_parity_circuit(&axis);
+
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 3);
+        let mut expected = Circuit::new(registers, Vec::new());
+        expected.append_gate("cx", &[0, 2], &[]).unwrap();
+        expected.append_gate("cx", &[1, 2], &[]).unwrap();
+        expected.operators.push(Operator::Measurement {
+            axis: Axis::new_with_pauli(3, 2, Pauli::Z),
+            target: 0,
+        });
+
+        assert_eq!(ops, expected.operators);
+    }
+
+    #[test]
+    fn test_measurement_to_parity_circuit_skips_identity_qubits() {
+        let axis = Axis::new(vec![Pauli::I, Pauli::Z, Pauli::I, Pauli::Z]);
+        let ops = measurement_to_parity_circuit(&axis);
+
+        // Only the two Z-support qubits (1 and 3) get CNOTs into the
+        // ancilla at index 4: 2 * 3 rotations plus the final measurement.
+        assert_eq!(ops.len(), 7);
+        assert!(matches!(
+            ops.last(),
+            Some(Operator::Measurement { axis, target: 0 }) if axis.get(4) == Pauli::Z
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported Pauli")]
+    fn test_measurement_to_parity_circuit_rejects_non_z_support() {
+        let axis = Axis::new(vec![Pauli::X]);
+        measurement_to_parity_circuit(&axis);
+    }
+}
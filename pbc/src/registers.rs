@@ -0,0 +1,209 @@
+/// The quantum and classical register layout of a circuit: an ordered list
+/// of named registers, each contributing a contiguous block of flat
+/// indices.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Registers {
+    qubit_registers: Vec<(String, usize)>,
+    cbit_registers: Vec<(String, usize)>,
+}
+
+impl Registers {
+    pub fn new() -> Registers {
+        Registers::default()
+    }
+
+    pub fn add_qubit_register(&mut self, name: &str, size: usize) {
+        self.qubit_registers.push((name.to_string(), size));
+    }
+
+    pub fn add_cbit_register(&mut self, name: &str, size: usize) {
+        self.cbit_registers.push((name.to_string(), size));
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.qubit_registers.iter().map(|(_, size)| size).sum()
+    }
+
+    pub fn num_cbits(&self) -> usize {
+        self.cbit_registers.iter().map(|(_, size)| size).sum()
+    }
+
+    pub fn qubit_registers(&self) -> &[(String, usize)] {
+        &self.qubit_registers
+    }
+
+    pub fn cbit_registers(&self) -> &[(String, usize)] {
+        &self.cbit_registers
+    }
+
+    /// Flat qubit index of `index` within register `name`, or `None` if no
+    /// such register or index exists.
+    pub fn qubit_index(&self, name: &str, index: usize) -> Option<usize> {
+        flat_index(&self.qubit_registers, name, index)
+    }
+
+    /// Flat classical-bit index of `index` within register `name`, or
+    /// `None` if no such register or index exists.
+    pub fn cbit_index(&self, name: &str, index: usize) -> Option<usize> {
+        flat_index(&self.cbit_registers, name, index)
+    }
+
+    /// The size of the qubit register `name`, if it exists.
+    pub fn qubit_register_size(&self, name: &str) -> Option<usize> {
+        self.qubit_registers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, size)| *size)
+    }
+
+    /// The size of the classical-bit register `name`, if it exists.
+    pub fn cbit_register_size(&self, name: &str) -> Option<usize> {
+        self.cbit_registers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, size)| *size)
+    }
+
+    /// The register name and in-register index of the qubit at flat index
+    /// `flat`, or `None` if out of range. The inverse of `qubit_index`.
+    pub fn qubit_name_and_index(&self, flat: usize) -> Option<(&str, usize)> {
+        let mut offset = 0;
+        for (name, size) in &self.qubit_registers {
+            if flat < offset + size {
+                return Some((name.as_str(), flat - offset));
+            }
+            offset += size;
+        }
+        None
+    }
+
+    /// Merges `other`'s registers into `self`, for combining circuits
+    /// parsed from separate files into one. A register name already
+    /// present in `self` is treated as the same physical register shared
+    /// across files, and must agree on size (a size mismatch is an
+    /// error); an unfamiliar name is appended after the existing
+    /// registers of that kind. Returns the flat-index mappings from
+    /// `other`'s own qubit and classical-bit indices to their new indices
+    /// in `self`, for remapping `other`'s operators.
+    pub fn merge(&mut self, other: &Registers) -> Result<(Vec<usize>, Vec<usize>), String> {
+        let qubit_map = merge_registers(&mut self.qubit_registers, &other.qubit_registers, "qubit")?;
+        let cbit_map = merge_registers(&mut self.cbit_registers, &other.cbit_registers, "classical-bit")?;
+        Ok((qubit_map, cbit_map))
+    }
+}
+
+fn flat_index(registers: &[(String, usize)], name: &str, index: usize) -> Option<usize> {
+    let mut offset = 0;
+    for (reg_name, size) in registers {
+        if reg_name == name {
+            return if index < *size {
+                Some(offset + index)
+            } else {
+                None
+            };
+        }
+        offset += size;
+    }
+    None
+}
+
+/// Merges `incoming` into `target` in place (appending any name not
+/// already present), returning `incoming`'s flat indices mapped onto
+/// `target`'s resulting layout.
+fn merge_registers(
+    target: &mut Vec<(String, usize)>,
+    incoming: &[(String, usize)],
+    kind: &str,
+) -> Result<Vec<usize>, String> {
+    let mut mapping = Vec::new();
+    for (name, size) in incoming {
+        let offset = match target.iter().position(|(n, _)| n == name) {
+            Some(pos) => {
+                let existing_size = target[pos].1;
+                if existing_size != *size {
+                    return Err(format!(
+                        "conflicting {} register '{}': size {} here vs size {} already declared",
+                        kind, name, size, existing_size
+                    ));
+                }
+                target[..pos].iter().map(|(_, s)| s).sum()
+            }
+            None => {
+                let offset: usize = target.iter().map(|(_, s)| s).sum();
+                target.push((name.clone(), *size));
+                offset
+            }
+        };
+        mapping.extend(offset..offset + size);
+    }
+    Ok(mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_indices() {
+        let mut regs = Registers::new();
+        regs.add_qubit_register("q", 2);
+        regs.add_qubit_register("anc", 3);
+
+        assert_eq!(regs.num_qubits(), 5);
+        assert_eq!(regs.qubit_index("q", 0), Some(0));
+        assert_eq!(regs.qubit_index("q", 1), Some(1));
+        assert_eq!(regs.qubit_index("anc", 0), Some(2));
+        assert_eq!(regs.qubit_index("anc", 2), Some(4));
+        assert_eq!(regs.qubit_index("anc", 3), None);
+        assert_eq!(regs.qubit_index("nope", 0), None);
+    }
+
+    #[test]
+    fn test_qubit_name_and_index() {
+        let mut regs = Registers::new();
+        regs.add_qubit_register("q", 2);
+        regs.add_qubit_register("anc", 3);
+
+        assert_eq!(regs.qubit_name_and_index(0), Some(("q", 0)));
+        assert_eq!(regs.qubit_name_and_index(1), Some(("q", 1)));
+        assert_eq!(regs.qubit_name_and_index(2), Some(("anc", 0)));
+        assert_eq!(regs.qubit_name_and_index(4), Some(("anc", 2)));
+        assert_eq!(regs.qubit_name_and_index(5), None);
+    }
+
+    #[test]
+    fn test_merge_shares_an_existing_register_and_appends_a_new_one() {
+        let mut a = Registers::new();
+        a.add_qubit_register("q", 2);
+        a.add_cbit_register("c", 2);
+
+        let mut b = Registers::new();
+        b.add_qubit_register("q", 2);
+        b.add_qubit_register("anc", 1);
+        b.add_cbit_register("c", 2);
+
+        let (qubit_map, cbit_map) = a.merge(&b).unwrap();
+
+        // "q" and "c" are shared, so b's flat indices 0 and 1 land on the
+        // same flat indices a already assigned them; "anc" is new, so it's
+        // appended after a's existing qubits.
+        assert_eq!(qubit_map, vec![0, 1, 2]);
+        assert_eq!(cbit_map, vec![0, 1]);
+        assert_eq!(a.num_qubits(), 3);
+        assert_eq!(a.num_cbits(), 2);
+        assert_eq!(a.qubit_registers(), &[("q".to_string(), 2), ("anc".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_merge_rejects_a_register_redeclared_with_a_different_size() {
+        let mut a = Registers::new();
+        a.add_qubit_register("q", 2);
+
+        let mut b = Registers::new();
+        b.add_qubit_register("q", 3);
+
+        let err = a.merge(&b).unwrap_err();
+        assert!(err.contains("q"), "error should name the conflicting register: {}", err);
+    }
+}
@@ -0,0 +1,70 @@
+use std::ops::{Mul, Neg};
+
+/// A fourth root of unity: `1`, `i`, `-1`, or `-i`. Needed because the
+/// product of two Pauli operators can pick up a factor of `i`, not just
+/// `-1` (e.g. `XY = iZ`), which a plain real-valued sign can't represent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Plus,
+    PlusI,
+    Minus,
+    MinusI,
+}
+
+impl Sign {
+    pub(crate) fn to_exponent(self) -> u32 {
+        match self {
+            Sign::Plus => 0,
+            Sign::PlusI => 1,
+            Sign::Minus => 2,
+            Sign::MinusI => 3,
+        }
+    }
+
+    pub(crate) fn from_exponent(exponent: u32) -> Sign {
+        match exponent % 4 {
+            0 => Sign::Plus,
+            1 => Sign::PlusI,
+            2 => Sign::Minus,
+            3 => Sign::MinusI,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Mul for Sign {
+    type Output = Sign;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Sign) -> Sign {
+        Sign::from_exponent(self.to_exponent() + other.to_exponent())
+    }
+}
+
+impl Neg for Sign {
+    type Output = Sign;
+
+    fn neg(self) -> Sign {
+        Sign::from_exponent(self.to_exponent() + 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul() {
+        assert_eq!(Sign::Plus * Sign::Minus, Sign::Minus);
+        assert_eq!(Sign::PlusI * Sign::PlusI, Sign::Minus);
+        assert_eq!(Sign::PlusI * Sign::MinusI, Sign::Plus);
+        assert_eq!(Sign::Minus * Sign::Minus, Sign::Plus);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Sign::Plus, Sign::Minus);
+        assert_eq!(-Sign::PlusI, Sign::MinusI);
+        assert_eq!(-Sign::Minus, Sign::Plus);
+    }
+}
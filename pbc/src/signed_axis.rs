@@ -0,0 +1,144 @@
+use std::ops::{Mul, Neg};
+
+use crate::axis::{from_symplectic_bits, to_symplectic_bits, Axis};
+use crate::operator::PauliRotation;
+use crate::pauli::Pauli;
+use crate::sign::Sign;
+
+/// The Pauli group product of two single-qubit Paulis, including the
+/// resulting sign/phase. Derived from writing each Pauli as `i^(x*z) *
+/// X^x * Z^z` in its symplectic `(x, z)` representation and using `Z X =
+/// -X Z`: the product's phase relative to its own symplectic label is
+/// `i^(c1 + c2 - c3 + 2 * z1 * x2)`, where `c = x * z` for each operand.
+fn multiply_paulis(a: Pauli, b: Pauli) -> (Pauli, Sign) {
+    let (x1, z1) = to_symplectic_bits(a);
+    let (x2, z2) = to_symplectic_bits(b);
+    let (x3, z3) = (x1 ^ x2, z1 ^ z2);
+
+    let c1 = u32::from(x1 && z1);
+    let c2 = u32::from(x2 && z2);
+    let c3 = u32::from(x3 && z3);
+    let cross = u32::from(z1 && x2);
+
+    // `c1 + c2 + 2 * cross` can be less than `c3` (e.g. `X * Z`), so the
+    // extra `4` (a full turn of `i`) keeps this from underflowing.
+    let exponent = c1 + c2 + 2 * cross + 4 - c3;
+    (from_symplectic_bits(x3, z3), Sign::from_exponent(exponent))
+}
+
+/// A full Pauli group element: a sign/phase (`1`, `i`, `-1`, or `-i`)
+/// together with an (unsigned) [`Axis`]. Plain `Axis` can't represent a
+/// signed operator like `-XYZ`, which forces callers that need one (e.g.
+/// tracking how a measurement's sign changes under conjugation) to fold
+/// the sign into something else, like the rotation angle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedAxis {
+    pub sign: Sign,
+    pub axis: Axis,
+}
+
+impl SignedAxis {
+    pub fn new(sign: Sign, axis: Axis) -> SignedAxis {
+        SignedAxis { sign, axis }
+    }
+
+    /// Conjugates this signed operator through the Clifford rotation
+    /// `frame_op`, mirroring the sign-free rule used elsewhere in the
+    /// crate (an axis commuting with `frame_op`'s axis is unaffected;
+    /// otherwise it's multiplied by it) but tracking the sign this
+    /// introduces, rather than discarding it.
+    pub fn conjugate_by(&self, frame_op: &PauliRotation) -> SignedAxis {
+        if self.axis.commutes_with(&frame_op.axis) {
+            self.clone()
+        } else {
+            self.clone() * SignedAxis::new(Sign::Plus, frame_op.axis.as_ref().clone())
+        }
+    }
+}
+
+impl Mul for SignedAxis {
+    type Output = SignedAxis;
+
+    fn mul(self, other: SignedAxis) -> SignedAxis {
+        assert_eq!(self.axis.width(), other.axis.width());
+        let mut sign = self.sign * other.sign;
+        let mut paulis = Vec::with_capacity(self.axis.width());
+        for (a, b) in self.axis.as_slice().iter().zip(other.axis.as_slice().iter()) {
+            let (pauli, pair_sign) = multiply_paulis(*a, *b);
+            sign = sign * pair_sign;
+            paulis.push(pauli);
+        }
+        SignedAxis::new(sign, Axis::new(paulis))
+    }
+}
+
+impl Neg for SignedAxis {
+    type Output = SignedAxis;
+
+    fn neg(self) -> SignedAxis {
+        SignedAxis::new(-self.sign, self.axis)
+    }
+}
+
+impl Neg for Axis {
+    type Output = SignedAxis;
+
+    fn neg(self) -> SignedAxis {
+        SignedAxis::new(Sign::Minus, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::Angle;
+    use crate::mod8::Mod8;
+
+    fn axis(paulis: &[Pauli]) -> Axis {
+        Axis::new(paulis.to_vec())
+    }
+
+    #[test]
+    fn test_multiply_paulis_single_qubit() {
+        assert_eq!(multiply_paulis(Pauli::X, Pauli::Y), (Pauli::Z, Sign::PlusI));
+        assert_eq!(multiply_paulis(Pauli::Y, Pauli::X), (Pauli::Z, Sign::MinusI));
+        assert_eq!(multiply_paulis(Pauli::X, Pauli::Z), (Pauli::Y, Sign::MinusI));
+        assert_eq!(multiply_paulis(Pauli::Z, Pauli::X), (Pauli::Y, Sign::PlusI));
+        assert_eq!(multiply_paulis(Pauli::X, Pauli::X), (Pauli::I, Sign::Plus));
+    }
+
+    #[test]
+    fn test_mul_tracks_sign_across_qubits() {
+        // (-XY) * (YX) = -(XY)(YX) = -(iZ)(-iZ) = -ZZ.
+        let lhs = SignedAxis::new(Sign::Minus, axis(&[Pauli::X, Pauli::Y]));
+        let rhs = SignedAxis::new(Sign::Plus, axis(&[Pauli::Y, Pauli::X]));
+        assert_eq!(lhs * rhs, SignedAxis::new(Sign::Minus, axis(&[Pauli::Z, Pauli::Z])));
+    }
+
+    #[test]
+    fn test_neg_axis_yields_signed_axis() {
+        assert_eq!(
+            -axis(&[Pauli::X]),
+            SignedAxis::new(Sign::Minus, axis(&[Pauli::X]))
+        );
+    }
+
+    #[test]
+    fn test_conjugate_by_commuting_rotation_is_unchanged() {
+        let signed = SignedAxis::new(Sign::Plus, axis(&[Pauli::Z]));
+        let frame_op = PauliRotation::new(axis(&[Pauli::Z]), Angle::PiOver8(Mod8::Two));
+        assert_eq!(signed.conjugate_by(&frame_op), signed);
+    }
+
+    #[test]
+    fn test_conjugate_by_anticommuting_rotation_tracks_sign() {
+        // A Z-measurement conjugated through an X frame rotation picks up
+        // the sign from Z * X = iY.
+        let signed = SignedAxis::new(Sign::Plus, axis(&[Pauli::Z]));
+        let frame_op = PauliRotation::new(axis(&[Pauli::X]), Angle::PiOver8(Mod8::Two));
+        assert_eq!(
+            signed.conjugate_by(&frame_op),
+            SignedAxis::new(Sign::PlusI, axis(&[Pauli::Y]))
+        );
+    }
+}
@@ -0,0 +1,926 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::angle::Angle;
+use crate::axis::{from_symplectic_bits, to_symplectic_bits, Axis};
+use crate::mod8::Mod8;
+use crate::operator::{Operator, PauliRotation};
+use crate::pauli::Pauli;
+use crate::sign::Sign;
+use crate::signed_axis::SignedAxis;
+
+/// Transforms `axis` through conjugation by the Clifford rotation `frame_op`.
+/// If they commute, `axis` is returned as-is with no allocation; otherwise
+/// it picks up `frame_op`'s axis via the (sign-free) Pauli product, which
+/// does allocate a fresh `Axis`. Taking and returning `axis` by `Rc` lets
+/// callers fold a rotation through a long frame without cloning the axis's
+/// underlying data at every step, only at the steps that actually change it.
+pub fn transform(axis: Rc<Axis>, frame_op: &PauliRotation) -> Rc<Axis> {
+    if axis.commutes_with(&frame_op.axis) {
+        axis
+    } else {
+        Rc::new(axis.multiply_ignoring_sign(&frame_op.axis))
+    }
+}
+
+/// Like `transform`, but computes the commutation check and the updated
+/// axis in a single pass over the two axes, instead of `commutes_with`'s
+/// full scan followed by a second full scan in `multiply_ignoring_sign`
+/// when they don't commute. Trades an always-allocated scratch `Vec` for
+/// never needing a second pass; see the `transform` vs
+/// `transform_single_pass` benchmark for which wins in practice. Returns
+/// the identical result to `transform` for every input.
+pub fn transform_single_pass(axis: Rc<Axis>, frame_op: &PauliRotation) -> Rc<Axis> {
+    let mut anticommuting_positions = 0usize;
+    let mut product = Vec::with_capacity(axis.width());
+    for (&a, &b) in axis.as_slice().iter().zip(frame_op.axis.as_slice().iter()) {
+        if a != Pauli::I && b != Pauli::I && a != b {
+            anticommuting_positions += 1;
+        }
+        let (ax, az) = to_symplectic_bits(a);
+        let (bx, bz) = to_symplectic_bits(b);
+        product.push(from_symplectic_bits(ax ^ bx, az ^ bz));
+    }
+
+    if anticommuting_positions.is_multiple_of(2) {
+        axis
+    } else {
+        Rc::new(Axis::new(product))
+    }
+}
+
+/// Shared implementation of `spc_translation`. `on_absorb` is called with
+/// the frame's current contents every time a Clifford rotation is pushed
+/// onto it, so callers that want to trace the frame's evolution (see
+/// `spc_translation_with_frame_trace`) can snapshot it without duplicating
+/// this loop; `spc_translation` itself passes a no-op closure.
+fn spc_translation_impl(
+    operators: &[Operator],
+    mut on_absorb: impl FnMut(&[PauliRotation]),
+) -> (Vec<Operator>, Vec<PauliRotation>) {
+    let mut frame: Vec<PauliRotation> = Vec::new();
+    let mut out = Vec::new();
+
+    for op in operators {
+        match op {
+            Operator::PauliRotation(rotation) => {
+                let mut axis = Rc::clone(&rotation.axis);
+                for frame_op in &frame {
+                    axis = transform(axis, frame_op);
+                }
+                match rotation.angle {
+                    Angle::PiOver8(n) if n.to_u32() % 2 == 0 => {
+                        frame.push(PauliRotation::new(axis, rotation.angle));
+                        on_absorb(&frame);
+                    }
+                    Angle::PiOver8(_) => {
+                        let (clifford, remainder) =
+                            PauliRotation::new(axis, rotation.angle).split_non_clifford();
+                        if let Some(clifford) = clifford {
+                            frame.push(clifford);
+                            on_absorb(&frame);
+                        }
+                        out.push(Operator::PauliRotation(remainder));
+                    }
+                    Angle::Arbitrary(_) => {
+                        out.push(Operator::PauliRotation(PauliRotation::new(
+                            axis,
+                            rotation.angle,
+                        )));
+                    }
+                }
+            }
+            Operator::Measurement { axis, target } => {
+                let mut axis = Rc::new(axis.clone());
+                for frame_op in &frame {
+                    axis = transform(axis, frame_op);
+                }
+                // No other owner holds this `Rc` once the fold is done, so
+                // this almost always unwraps for free rather than cloning.
+                let axis = Rc::try_unwrap(axis).unwrap_or_else(|shared| (*shared).clone());
+                out.push(Operator::Measurement { axis, target: *target });
+            }
+            Operator::Reset { qubit } => {
+                // The qubit comes back in a fresh, known state, so any
+                // frame entry that still acts on it no longer describes a
+                // real correlation; drop it instead of conjugating past it.
+                frame.retain(|frame_op| frame_op.axis.get(*qubit) == Pauli::I);
+            }
+            Operator::Barrier(qubits) => {
+                // A barrier carries no quantum operation, so it neither
+                // grows the Clifford frame nor needs conjugating -- it
+                // passes straight through.
+                out.push(Operator::Barrier(qubits.clone()));
+            }
+            Operator::Conditional { cbits, value, inner } => {
+                // A conditional might not fire at runtime, so -- unlike an
+                // unconditional rotation -- it can never be absorbed into
+                // the frame: its inner operator is conjugated through the
+                // frame as it stands, but always emitted, never folded in.
+                let transformed = transform_through_frame(&frame, inner);
+                out.push(Operator::Conditional {
+                    cbits: cbits.clone(),
+                    value: *value,
+                    inner: Box::new(transformed),
+                });
+            }
+        }
+    }
+
+    (out, frame)
+}
+
+/// Conjugates `op`'s axis (if it has one) through `frame`, without
+/// mutating `frame` or absorbing anything into it; used for a
+/// `Conditional`'s inner operator, which must never join the frame itself
+/// since whether it fires is only known at runtime. Recurses for a nested
+/// `Conditional`; every other axis-less variant passes through unchanged.
+fn transform_through_frame(frame: &[PauliRotation], op: &Operator) -> Operator {
+    match op {
+        Operator::PauliRotation(rotation) => {
+            let mut axis = Rc::clone(&rotation.axis);
+            for frame_op in frame {
+                axis = transform(axis, frame_op);
+            }
+            Operator::PauliRotation(PauliRotation::new(axis, rotation.angle))
+        }
+        Operator::Measurement { axis, target } => {
+            let mut axis = Rc::new(axis.clone());
+            for frame_op in frame {
+                axis = transform(axis, frame_op);
+            }
+            let axis = Rc::try_unwrap(axis).unwrap_or_else(|shared| (*shared).clone());
+            Operator::Measurement { axis, target: *target }
+        }
+        Operator::Reset { .. } | Operator::Barrier(_) => op.clone(),
+        Operator::Conditional { cbits, value, inner } => Operator::Conditional {
+            cbits: cbits.clone(),
+            value: *value,
+            inner: Box::new(transform_through_frame(frame, inner)),
+        },
+    }
+}
+
+/// Translates a circuit's operators into Pauli-based computation form:
+/// Clifford gates are absorbed into a running frame, and every axis in the
+/// output is re-expressed in terms of the original (pre-circuit) qubits.
+/// A `Reset` clears any frame entry that still acts on its qubit, since
+/// that qubit is starting over in a known state rather than continuing to
+/// carry whatever correlations the earlier Cliffords encoded for it. The
+/// output contains only non-Clifford (magic-state) rotations and
+/// measurements, plus any barriers and conditionals, which are passed
+/// through (a conditional's inner operator conjugated through the frame)
+/// rather than absorbed.
+pub fn spc_translation(operators: &[Operator]) -> Vec<Operator> {
+    spc_translation_impl(operators, |_| {}).0
+}
+
+/// Like `spc_translation`, but also returns a snapshot of the Clifford
+/// frame taken immediately after each Clifford rotation is absorbed into
+/// it -- i.e. the circuit's accumulated Clifford conjugation, re-expressed
+/// in terms of the original qubits, at each step. Used by `--trace-frame`
+/// to show researchers how that conjugation evolves gate by gate.
+pub fn spc_translation_with_frame_trace(operators: &[Operator]) -> (Vec<Operator>, Vec<Vec<PauliRotation>>) {
+    let mut trace = Vec::new();
+    let (out, _frame) = spc_translation_impl(operators, |frame| trace.push(frame.to_vec()));
+    (out, trace)
+}
+
+/// Like `spc_translation`, but appends the residual Clifford frame as
+/// explicit `PauliRotation`s at the end of the output. `spc_translation`
+/// drops the frame once translation finishes, since everything that comes
+/// after it has already been re-expressed through it; this variant keeps
+/// it around as real operators instead, so the result is a faithful (not
+/// just logically-equivalent) circuit that consumers can run as-is.
+pub fn spc_translation_with_frame_cleanup(operators: &[Operator]) -> Vec<Operator> {
+    let (mut out, frame) = spc_translation_impl(operators, |_| {});
+    out.extend(frame.into_iter().map(Operator::PauliRotation));
+    out
+}
+
+/// Like `spc_translation`, but rejects the result if any measurement's
+/// axis came out all-identity -- degenerate, since it measures nothing.
+/// That can happen with bad input, but also legitimately: conjugating a
+/// measurement through the Clifford frame can cancel it down to identity.
+/// `spc_translation` itself stays silent about this (its signature is
+/// depended on too widely to make it fallible), so use this variant
+/// wherever a caller wants to catch the condition instead of passing it
+/// through.
+pub fn spc_translation_checked(operators: &[Operator]) -> Result<Vec<Operator>, String> {
+    let out = spc_translation(operators);
+    let degenerate = crate::analysis::identity_measurements(&out);
+    if degenerate.is_empty() {
+        Ok(out)
+    } else {
+        Err(format!(
+            "spc_translation produced {} all-identity measurement(s) at output index/indices {:?}; each measures nothing",
+            degenerate.len(),
+            degenerate
+        ))
+    }
+}
+
+/// The `(qubit, pauli)` a rotation's axis acts on, if it touches exactly
+/// one qubit non-trivially (and `None` for an all-identity or multi-qubit
+/// axis).
+fn single_qubit_support(axis: &Axis) -> Option<(usize, Pauli)> {
+    let mut found = None;
+    for (qubit, pauli) in axis.as_slice().iter().enumerate() {
+        if *pauli != Pauli::I {
+            if found.is_some() {
+                return None;
+            }
+            found = Some((qubit, *pauli));
+        }
+    }
+    found
+}
+
+/// Adds two rotation angles, the way composing two rotations about the
+/// same axis does: `PiOver8` numerators add mod 8, and anything involving
+/// an `Arbitrary` angle falls back to floating-point radians.
+fn add_angles(a: Angle, b: Angle) -> Angle {
+    match (a, b) {
+        (Angle::PiOver8(x), Angle::PiOver8(y)) => Angle::PiOver8(Mod8::from(x.to_u32() + y.to_u32())),
+        (a, b) => Angle::Arbitrary(a.to_radians() + b.to_radians()),
+    }
+}
+
+/// Whether `window` is exactly the five single-qubit rotations adjacent
+/// fusion reduces two back-to-back `H` gates on the same qubit down to:
+/// `H` is `Z(pi/4) X(pi/4) Z(pi/4)`, so `H;H` is `Z2 X2 Z2 Z2 X2 Z2`, and the
+/// middle `Z2 Z2` has already fused into `Z4` by the time this runs. `H`
+/// squares to exactly the identity (it's its own inverse, with no leftover
+/// global phase), so this specific shape on one qubit is itself the
+/// identity and can be dropped entirely.
+fn is_double_hadamard(window: &[Operator]) -> bool {
+    const SHAPE: [(Pauli, Mod8); 5] = [
+        (Pauli::Z, Mod8::Two),
+        (Pauli::X, Mod8::Two),
+        (Pauli::Z, Mod8::Four),
+        (Pauli::X, Mod8::Two),
+        (Pauli::Z, Mod8::Two),
+    ];
+
+    let qubit = match window.first().and_then(Operator::axis).and_then(single_qubit_support) {
+        Some((qubit, _)) => qubit,
+        None => return false,
+    };
+
+    window.iter().zip(SHAPE).all(|(op, (pauli, angle))| match op {
+        Operator::PauliRotation(r) => {
+            r.angle == Angle::PiOver8(angle) && single_qubit_support(&r.axis) == Some((qubit, pauli))
+        }
+        _ => false,
+    })
+}
+
+/// Drops every contiguous `is_double_hadamard` window in `ops`, in place.
+/// Doesn't advance past a removal, so e.g. four back-to-back `H` gates on
+/// the same qubit (two double-`H` patterns in a row once fused) both get
+/// dropped in one pass.
+fn cancel_double_hadamards(ops: &mut Vec<Operator>) {
+    let mut i = 0;
+    while i + 5 <= ops.len() {
+        if is_double_hadamard(&ops[i..i + 5]) {
+            ops.drain(i..i + 5);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// A pre-pass ahead of `spc_translation` that fuses adjacent single-qubit
+/// rotations about the same axis into one, by angle addition: since a
+/// smaller Clifford frame absorbs faster and prints shorter, collapsing a
+/// run of e.g. several `S` rotations on the same qubit into a single
+/// rotation is worth doing before translation even starts. It also
+/// recognizes and drops two back-to-back `H` gates on the same qubit once
+/// fusion reduces them to `is_double_hadamard`'s shape, since that's the
+/// identity.
+///
+/// A pending rotation on a qubit stays eligible for fusion across any
+/// later operator it can be swapped past (see `Operator::can_swap_with`) --
+/// most commonly another rotation on a disjoint qubit -- and stops being
+/// eligible the moment it can't, e.g. an anticommuting rotation on the
+/// same qubit, or a `Reset` of that qubit.
+pub fn peephole_fuse_single_qubit(ops: &[Operator]) -> Vec<Operator> {
+    let mut out: Vec<Operator> = Vec::new();
+    let mut pending: HashMap<usize, usize> = HashMap::new();
+
+    for op in ops {
+        if let Operator::PauliRotation(rotation) = op {
+            if let Some((qubit, _)) = single_qubit_support(&rotation.axis) {
+                if let Some(&index) = pending.get(&qubit) {
+                    if let Operator::PauliRotation(existing) = &out[index] {
+                        if existing.axis == rotation.axis {
+                            let angle = add_angles(existing.angle, rotation.angle);
+                            out[index] =
+                                Operator::PauliRotation(PauliRotation::new(existing.axis.clone(), angle));
+                            continue;
+                        }
+                    }
+                }
+                pending.retain(|_, index| out[*index].can_swap_with(op));
+                out.push(op.clone());
+                pending.insert(qubit, out.len() - 1);
+                continue;
+            }
+        }
+
+        pending.retain(|_, index| out[*index].can_swap_with(op));
+        out.push(op.clone());
+    }
+
+    // A fused run can cancel out entirely (e.g. two `S` rotations fusing
+    // into a `Z`, or a rotation fusing with its own inverse); drop the
+    // resulting no-ops rather than emitting a rotation that does nothing.
+    out.retain(|op| !matches!(op, Operator::PauliRotation(r) if matches!(r.angle, Angle::PiOver8(Mod8::Zero))));
+    cancel_double_hadamards(&mut out);
+    out
+}
+
+/// The sign a logical qubit's X and/or Z operator picks up from `operators`'
+/// absorbed Clifford frame (see `spc_translation`), relative to its
+/// starting sign of `Sign::Plus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalFrameSign {
+    pub qubit: usize,
+    pub x_sign: Sign,
+    pub z_sign: Sign,
+}
+
+/// Conjugates `signed` through each of `frame`'s rotations in order, the
+/// way `transform` folds a bare axis through the frame, but tracking the
+/// sign that introduces via `SignedAxis::conjugate_by` instead of
+/// discarding it.
+fn transform_signed(signed: SignedAxis, frame: &[PauliRotation]) -> SignedAxis {
+    frame.iter().fold(signed, |signed, frame_op| signed.conjugate_by(frame_op))
+}
+
+/// For every logical qubit in `operators`, conjugates its (unsigned, i.e.
+/// `Sign::Plus`) X and Z operators through the circuit's absorbed Clifford
+/// frame and reports the ones whose sign came out changed.
+///
+/// `spc_translation` itself discards sign when folding rotations through
+/// the frame (`transform` multiplies axes "ignoring sign"), since SPC form
+/// doesn't need it; this recomputes the same conjugation with `SignedAxis`
+/// for callers -- e.g. verification against a reference unitary, which
+/// does care about phase -- that need to know whether translation
+/// introduced a relative sign on a logical operator. Width is the widest
+/// axis seen in `operators` (empty if none touch any qubits), matching
+/// `t_count_per_qubit`.
+pub fn logical_frame_sign_changes(operators: &[Operator]) -> Vec<LogicalFrameSign> {
+    let (_, frame) = spc_translation_impl(operators, |_| {});
+    let width = operators
+        .iter()
+        .filter_map(Operator::axis)
+        .map(|axis| axis.as_slice().len())
+        .max()
+        .unwrap_or(0);
+
+    (0..width)
+        .filter_map(|qubit| {
+            let x = SignedAxis::new(Sign::Plus, Axis::new_with_pauli(width, qubit, Pauli::X));
+            let z = SignedAxis::new(Sign::Plus, Axis::new_with_pauli(width, qubit, Pauli::Z));
+            let x_sign = transform_signed(x, &frame).sign;
+            let z_sign = transform_signed(z, &frame).sign;
+            if x_sign != Sign::Plus || z_sign != Sign::Plus {
+                Some(LogicalFrameSign { qubit, x_sign, z_sign })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pauli::Pauli;
+    use crate::test_support::{random_axis, Rng};
+
+    fn test_transform_rotation_cases() -> Vec<(Axis, PauliRotation)> {
+        vec![
+            // Commuting: disjoint qubits.
+            (
+                Axis::new_with_pauli(2, 0, Pauli::X),
+                PauliRotation::new(Axis::new_with_pauli(2, 1, Pauli::Z), Angle::PiOver8(Mod8::Two)),
+            ),
+            // Commuting: same axis.
+            (
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::Z), Angle::PiOver8(Mod8::Two)),
+            ),
+            // Anticommuting: single qubit.
+            (
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::Z), Angle::PiOver8(Mod8::Two)),
+            ),
+            // Anticommuting: multi-qubit axis.
+            (
+                Axis::new(vec![Pauli::X, Pauli::X, Pauli::I]),
+                PauliRotation::new(Axis::new(vec![Pauli::I, Pauli::Z, Pauli::Z]), Angle::PiOver8(Mod8::Six)),
+            ),
+            // A Clifford angle that isn't One/Seven-adjacent, to cover the
+            // angle space `spc_translation_impl` actually absorbs into the
+            // frame.
+            (
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::Z), Angle::PiOver8(Mod8::Zero)),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_transform_single_pass_matches_transform_on_every_case() {
+        for (axis, frame_op) in test_transform_rotation_cases() {
+            let expected = transform(Rc::new(axis.clone()), &frame_op);
+            let actual = transform_single_pass(Rc::new(axis), &frame_op);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_transform_single_pass_matches_transform_on_random_axes() {
+        let mut rng = Rng::new(0x5eed);
+        for width in [1, 2, 8, 64] {
+            let axis = random_axis(&mut rng, width);
+            let frame_op = PauliRotation::new(random_axis(&mut rng, width), Angle::PiOver8(Mod8::Four));
+            let expected = transform(Rc::new(axis.clone()), &frame_op);
+            let actual = transform_single_pass(Rc::new(axis), &frame_op);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_pure_clifford_circuit_yields_no_output() {
+        let ops = vec![Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Two),
+        ))];
+        assert_eq!(spc_translation(&ops), vec![]);
+    }
+
+    #[test]
+    fn test_t_gate_passes_through_unchanged() {
+        let ops = vec![Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        ))];
+        assert_eq!(spc_translation(&ops), ops);
+    }
+
+    #[test]
+    fn test_measurement_transformed_through_frame() {
+        // x q[0]; measure q[0] -> c[0];
+        // X and Z anticommute, so the measurement axis picks up the frame's
+        // X via the (sign-free) Pauli product, becoming a Y measurement.
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(1, 0, Pauli::Z),
+                target: 0,
+            },
+        ];
+        let translated = spc_translation(&ops);
+        assert_eq!(
+            translated,
+            vec![Operator::Measurement {
+                axis: Axis::new_with_pauli(1, 0, Pauli::Y),
+                target: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_spc_translation_checked_rejects_an_all_identity_measurement() {
+        let ops = vec![Operator::Measurement {
+            axis: Axis::identity(4),
+            target: 0,
+        }];
+        assert_eq!(spc_translation(&ops), ops);
+        let err = spc_translation_checked(&ops).unwrap_err();
+        assert!(err.contains('0'), "error should mention the degenerate index: {}", err);
+    }
+
+    #[test]
+    fn test_spc_translation_checked_accepts_a_real_measurement() {
+        let ops = vec![Operator::Measurement {
+            axis: Axis::new_with_pauli(4, 0, Pauli::Z),
+            target: 0,
+        }];
+        assert_eq!(spc_translation_checked(&ops), Ok(ops));
+    }
+
+    /// A circuit of `depth` random rotations and measurements over `width`
+    /// qubits, like `test_support::random_circuit`, except every rotation's
+    /// angle is restricted to `pi/8` or `-pi/8` (`Mod8::One`/`Seven`). Those
+    /// are the only non-Clifford angles `split_non_clifford` contributes no
+    /// Clifford part for, so a circuit built entirely from them never
+    /// absorbs anything into the frame: translation leaves every axis
+    /// exactly as it found it, which is what lets the test below compare
+    /// commutation before and after without the frame's own conjugation
+    /// getting in the way.
+    fn random_non_clifford_circuit(seed: u64, width: usize, depth: usize) -> Vec<Operator> {
+        let mut rng = Rng::new(seed);
+        (0..depth)
+            .map(|i| {
+                let axis = random_axis(&mut rng, width);
+                if i % 2 == 0 {
+                    let angle = if rng.below(2) == 0 { Mod8::One } else { Mod8::Seven };
+                    Operator::PauliRotation(PauliRotation::new(axis, Angle::PiOver8(angle)))
+                } else {
+                    Operator::Measurement { axis, target: i }
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_spc_translation_preserves_commutation_among_non_clifford_operators() {
+        // `spc_translation` conjugates every operator by a running Clifford
+        // frame. Conjugation by a Clifford is an automorphism of the Pauli
+        // group, so it must preserve commutation: the non-Clifford
+        // operators should have the exact same pairwise commutation
+        // structure before and after translation, even though their axes
+        // themselves change.
+        use crate::analysis::commutation_matrix;
+
+        let fixtures: Vec<Vec<Operator>> = vec![
+            random_non_clifford_circuit(1, 3, 20),
+            random_non_clifford_circuit(2, 4, 30),
+            random_non_clifford_circuit(3, 1, 10),
+            vec![
+                Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(2, 0, Pauli::Z),
+                    Angle::PiOver8(crate::mod8::Mod8::Two),
+                )),
+                Operator::PauliRotation(PauliRotation::new(
+                    Axis::new(vec![Pauli::X, Pauli::X]),
+                    Angle::PiOver8(crate::mod8::Mod8::One),
+                )),
+                Operator::Measurement { axis: Axis::new(vec![Pauli::Z, Pauli::Y]), target: 0 },
+            ],
+        ];
+
+        for ops in fixtures {
+            let translated = spc_translation(&ops);
+
+            // Clifford rotations and resets are absorbed into the frame
+            // and never appear in the output, so only non-Clifford
+            // rotations and measurements have a counterpart to compare.
+            let kept: Vec<&Operator> = ops
+                .iter()
+                .filter(|op| op.is_measurement() || !op.is_clifford())
+                .collect();
+            assert_eq!(kept.len(), translated.len());
+
+            let before = commutation_matrix(&kept.iter().map(|op| (*op).clone()).collect::<Vec<_>>());
+            let after = commutation_matrix(&translated);
+            for i in 0..kept.len() {
+                for j in 0..kept.len() {
+                    assert_eq!(
+                        before[i][j], after[i][j],
+                        "commutation of ({}, {}) changed under translation",
+                        i, j
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_frame_fold_does_not_allocate_per_entry() {
+        // 1000 identical Z rotations (all Clifford, so each joins the
+        // frame), followed by a T gate that must fold through all of them.
+        // Every fold step commutes (Z with Z), so `transform` should hand
+        // back the same `Rc` rather than allocating a fresh `Axis` each
+        // time: total allocations should stay a small constant, not one
+        // per frame entry (which would be ~500,000 for 1000 entries under
+        // the naive clone-on-every-fold approach).
+        let width = 4;
+        let mut ops: Vec<Operator> = (0..1000)
+            .map(|_| {
+                Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(width, 0, Pauli::Z),
+                    Angle::PiOver8(Mod8::Two),
+                ))
+            })
+            .collect();
+        ops.push(Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(width, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        )));
+
+        let before = crate::alloc_count::allocation_count();
+        let translated = spc_translation(&ops);
+        let after = crate::alloc_count::allocation_count();
+
+        assert_eq!(translated.len(), 1);
+        let allocations = after - before;
+        assert!(
+            allocations < 50,
+            "expected a small constant number of allocations, got {}",
+            allocations
+        );
+    }
+
+    #[test]
+    fn test_reset_clears_frame_contributions_on_its_qubit() {
+        // x q[0]; reset q[0]; t q[0];
+        // Without the reset, the T gate's Z axis would anticommute with the
+        // frame's X and come out as Y. The reset drops that frame entry, so
+        // the T gate passes through unchanged.
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            Operator::Reset { qubit: 0 },
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+        ];
+        let translated = spc_translation(&ops);
+        assert_eq!(
+            translated,
+            vec![Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_conditional_inner_axis_is_conjugated_through_the_frame_but_never_absorbed() {
+        // x q[0]; if (c==1) z q[0];
+        // The frame's X turns the conditional's Z axis into a Y; the Clifford
+        // even-numerator angle on it would normally absorb into the frame,
+        // but a conditional must never do that, since it might not fire.
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            Operator::Conditional {
+                cbits: vec![0],
+                value: 1,
+                inner: Box::new(Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(1, 0, Pauli::Z),
+                    Angle::PiOver8(Mod8::Four),
+                ))),
+            },
+        ];
+        let translated = spc_translation(&ops);
+        assert_eq!(
+            translated,
+            vec![Operator::Conditional {
+                cbits: vec![0],
+                value: 1,
+                inner: Box::new(Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(1, 0, Pauli::Y),
+                    Angle::PiOver8(Mod8::Four),
+                ))),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reset_only_clears_the_frame_on_its_own_qubit() {
+        // x q[0]; x q[1]; reset q[0]; measure q[1] -> c[0];
+        // The frame's X on qubit 1 survives the reset of qubit 0, so the
+        // measurement still picks it up and becomes a Y measurement.
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 1, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            Operator::Reset { qubit: 0 },
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(2, 1, Pauli::Z),
+                target: 0,
+            },
+        ];
+        let translated = spc_translation(&ops);
+        assert_eq!(
+            translated,
+            vec![Operator::Measurement {
+                axis: Axis::new_with_pauli(2, 1, Pauli::Y),
+                target: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_frame_trace_records_a_snapshot_after_each_absorbed_clifford() {
+        // x q[0]; z q[0]; t q[0];
+        // The X lands in the frame unchanged; the Z anticommutes with it
+        // and picks up the X via the (sign-free) Pauli product, landing in
+        // the frame as Y. The T gate is non-Clifford and leaves the frame
+        // as-is.
+        let x = PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::X), Angle::PiOver8(Mod8::Four));
+        let z_through_frame =
+            PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::Y), Angle::PiOver8(Mod8::Four));
+        let ops = vec![
+            Operator::PauliRotation(x.clone()),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+        ];
+
+        let (translated, trace) = spc_translation_with_frame_trace(&ops);
+
+        assert_eq!(translated, spc_translation(&ops));
+        assert_eq!(trace.len(), 2);
+        assert_eq!(trace[0], vec![x.clone()]);
+        assert_eq!(trace[1], vec![x, z_through_frame]);
+    }
+
+    #[test]
+    fn test_frame_cleanup_appends_the_residual_frame_as_rotations() {
+        // x q[0]; t q[0];
+        // The X lands in the frame; the default translation drops it once
+        // the T gate has been re-expressed through it, while the cleanup
+        // variant keeps it as a trailing rotation.
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+        ];
+
+        let default = spc_translation(&ops);
+        let with_cleanup = spc_translation_with_frame_cleanup(&ops);
+
+        assert_eq!(with_cleanup.len(), default.len() + 1);
+        assert_eq!(&with_cleanup[..default.len()], default.as_slice());
+        assert_eq!(
+            with_cleanup.last(),
+            Some(&Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Four),
+            )))
+        );
+    }
+
+    fn h(qubit: usize, width: usize) -> Vec<Operator> {
+        vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(width, qubit, Pauli::Z),
+                Angle::PiOver8(Mod8::Two),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(width, qubit, Pauli::X),
+                Angle::PiOver8(Mod8::Two),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(width, qubit, Pauli::Z),
+                Angle::PiOver8(Mod8::Two),
+            )),
+        ]
+    }
+
+    #[test]
+    fn test_peephole_fuse_single_qubit_cancels_an_h_followed_by_another_h() {
+        let mut ops = h(0, 1);
+        ops.extend(h(0, 1));
+
+        let fused = peephole_fuse_single_qubit(&ops);
+        assert_eq!(fused, Vec::new());
+
+        // Fusing is a peephole optimization: it must not change what the
+        // circuit translates to.
+        assert_eq!(spc_translation(&fused), spc_translation(&ops));
+    }
+
+    #[test]
+    fn test_peephole_fuse_single_qubit_survives_a_disjoint_qubit_operator() {
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 1, Pauli::X),
+                Angle::PiOver8(Mod8::Two),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+        ];
+
+        assert_eq!(
+            peephole_fuse_single_qubit(&ops),
+            vec![
+                Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(2, 0, Pauli::Z),
+                    Angle::PiOver8(Mod8::Two),
+                )),
+                Operator::PauliRotation(PauliRotation::new(
+                    Axis::new_with_pauli(2, 1, Pauli::X),
+                    Angle::PiOver8(Mod8::Two),
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_peephole_fuse_single_qubit_mixes_piover8_and_arbitrary_as_radians() {
+        // PiOver8(Mod8::One) is pi/8 radians; fused with an Arbitrary 0.2
+        // radian rotation on the same axis, the result should be their sum
+        // in radians, not some other scaling.
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+            Operator::PauliRotation(PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::Z), Angle::Arbitrary(0.2))),
+        ];
+
+        let fused = peephole_fuse_single_qubit(&ops);
+        assert_eq!(
+            fused,
+            vec![Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::Arbitrary(std::f64::consts::PI / 8.0 + 0.2),
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_peephole_fuse_single_qubit_a_reset_breaks_the_run() {
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+            Operator::Reset { qubit: 0 },
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(1, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+        ];
+
+        assert_eq!(peephole_fuse_single_qubit(&ops), ops);
+    }
+
+    #[test]
+    fn test_split_non_clifford() {
+        let ops = vec![Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Three),
+        ))];
+        let translated = spc_translation(&ops);
+        assert_eq!(translated.len(), 1);
+        match &translated[0] {
+            Operator::PauliRotation(r) => assert_eq!(r.angle, Angle::PiOver8(Mod8::Seven)),
+            _ => panic!("expected a rotation"),
+        }
+    }
+
+    #[test]
+    fn test_logical_frame_sign_changes_on_an_s_gate() {
+        // S = exp(-i pi/4 Z) conjugates X to Y with a sign of -i (since
+        // X * Z = -iY), and leaves Z unchanged (it commutes with itself).
+        let ops = vec![Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::Two),
+        ))];
+
+        let changes = logical_frame_sign_changes(&ops);
+
+        assert_eq!(
+            changes,
+            vec![LogicalFrameSign { qubit: 0, x_sign: Sign::MinusI, z_sign: Sign::Plus }]
+        );
+    }
+
+    #[test]
+    fn test_logical_frame_sign_changes_reports_nothing_for_a_non_clifford_rotation() {
+        let ops = vec![Operator::PauliRotation(PauliRotation::new(
+            Axis::new_with_pauli(1, 0, Pauli::Z),
+            Angle::PiOver8(Mod8::One),
+        ))];
+
+        assert_eq!(logical_frame_sign_changes(&ops), vec![]);
+    }
+}
@@ -0,0 +1,384 @@
+use std::rc::Rc;
+
+use crate::angle::Angle;
+use crate::axis::Axis;
+use crate::mod8::Mod8;
+use crate::operator::{Operator, PauliRotation};
+use crate::pauli::Pauli;
+use crate::sign::Sign;
+use crate::signed_axis::SignedAxis;
+use crate::spc::{spc_translation, transform};
+
+/// One step of a compact translation: an `operator` plus the single-qubit
+/// Clifford corrections (`cliffords`) that, applied to its axis in order,
+/// diagonalize it to `Z`/`I` only, and the `sign` that diagonalization
+/// introduces relative to the operator's own (unsigned) axis. For a
+/// measurement, `sign` is the factor the diagonalized `Z`/`I` outcome must
+/// be multiplied by to recover the original axis's outcome; for a
+/// rotation it's carried for the same reason but has no standalone
+/// observable meaning, since the rotation angle already fixes its sense.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactStep {
+    pub cliffords: Vec<PauliRotation>,
+    pub sign: Sign,
+    pub operator: Operator,
+}
+
+/// The single-qubit Clifford rotations that diagonalize `axis` into an axis
+/// containing only `Z` and `I`: `X` and `Y` each anticommute with exactly
+/// one of the other, so conjugating the `X`/`Y` position by a same-qubit
+/// `Y`/`X` rotation (respectively) swaps it to `Z`, while `Z`/`I` positions
+/// are left alone.
+fn diagonalizing_cliffords(axis: &Axis) -> Vec<PauliRotation> {
+    let mut cliffords = Vec::new();
+    for i in 0..axis.width() {
+        let correction = match axis.get(i) {
+            Pauli::X => Some(Pauli::Y),
+            Pauli::Y => Some(Pauli::X),
+            Pauli::Z | Pauli::I => None,
+        };
+        if let Some(pauli) = correction {
+            cliffords.push(PauliRotation::new(
+                Axis::new_with_pauli(axis.width(), i, pauli),
+                Angle::PiOver8(Mod8::Two),
+            ));
+        }
+    }
+    cliffords
+}
+
+/// The qubit indices `rotation`'s axis acts non-trivially on.
+fn touched_qubits(rotation: &PauliRotation) -> Vec<usize> {
+    rotation
+        .axis
+        .as_slice()
+        .iter()
+        .enumerate()
+        .filter(|(_, pauli)| **pauli != Pauli::I)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The number of sequential hardware "clocks" needed to apply `cliffords`
+/// in their given order: corrections on disjoint qubits can run in the
+/// same clock, while corrections sharing a qubit need separate, ordered
+/// clocks. Greedily packs each correction into the earliest clock whose
+/// qubits don't overlap it yet.
+///
+/// `diagonalizing_cliffords` emits at most one correction per qubit, so
+/// its output is always fully disjoint and collapses to a single clock
+/// here, regardless of how many `X`/`Y` positions the axis has.
+pub fn additional_clocks(cliffords: &[PauliRotation]) -> usize {
+    let mut clocks: Vec<Vec<usize>> = Vec::new();
+    for rotation in cliffords {
+        let qubits = touched_qubits(rotation);
+        let slot = clocks
+            .iter()
+            .position(|used| !used.iter().any(|q| qubits.contains(q)));
+        match slot {
+            Some(i) => clocks[i].extend(qubits),
+            None => clocks.push(qubits),
+        }
+    }
+    clocks.len()
+}
+
+/// The sign `cliffords` introduces when diagonalizing `axis`, tracked by
+/// conjugating the (initially unsigned) axis through each correction in
+/// order via `SignedAxis::conjugate_by` -- the sign-aware counterpart of
+/// the plain `transform` calls `verify_compact` uses to check the same
+/// corrections actually reach `Z`/`I`.
+fn diagonalizing_sign(axis: &Axis, cliffords: &[PauliRotation]) -> Sign {
+    cliffords
+        .iter()
+        .fold(SignedAxis::new(Sign::Plus, axis.clone()), |signed, clifford| signed.conjugate_by(clifford))
+        .sign
+}
+
+/// Translates `operators` like [`spc_translation`], then records, for each
+/// output operator, the Clifford corrections that diagonalize its axis to
+/// `Z`/`I` only, along with the sign those corrections introduce. An output
+/// operator with no axis (a barrier) gets no corrections and `Sign::Plus`.
+pub fn spc_compact_translation(operators: &[Operator]) -> Vec<CompactStep> {
+    spc_translation(operators)
+        .into_iter()
+        .map(|operator| {
+            let (cliffords, sign) = match operator.axis() {
+                Some(axis) => {
+                    let cliffords = diagonalizing_cliffords(axis);
+                    let sign = diagonalizing_sign(axis, &cliffords);
+                    (cliffords, sign)
+                }
+                None => (Vec::new(), Sign::Plus),
+            };
+            CompactStep { cliffords, sign, operator }
+        })
+        .collect()
+}
+
+/// A permutation (in `Operator::map_axis`'s convention: `permutation[i]` is
+/// the new index of old qubit `i`) that moves every qubit with `X`/`Y`
+/// support anywhere in `operators` to the front, in their original relative
+/// order, so qubits that need diagonalizing corrections are co-located
+/// rather than scattered across the register.
+fn xy_colocating_permutation(operators: &[Operator], width: usize) -> Vec<usize> {
+    let mut has_xy = vec![false; width];
+    for op in operators {
+        if let Some(axis) = op.axis() {
+            for (qubit, pauli) in axis.as_slice().iter().enumerate() {
+                if matches!(pauli, Pauli::X | Pauli::Y) {
+                    has_xy[qubit] = true;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..width).collect();
+    order.sort_by_key(|&qubit| !has_xy[qubit]);
+
+    let mut permutation = vec![0; width];
+    for (new_index, old_qubit) in order.into_iter().enumerate() {
+        permutation[old_qubit] = new_index;
+    }
+    permutation
+}
+
+/// Like [`spc_compact_translation`], but first permutes `operators`' qubits
+/// to co-locate `X`/`Y`-support qubits (see `xy_colocating_permutation`),
+/// returning that permutation alongside the resulting steps so the result
+/// stays interpretable against the original qubit numbering.
+///
+/// Within this crate's one-correction-per-qubit diagonalization (see
+/// `diagonalizing_cliffords`), a single operator's corrections are always
+/// on disjoint qubits and already collapse to the minimum of one clock
+/// (or zero, if it has no `X`/`Y` support at all) regardless of qubit
+/// order, so reordering can't reduce `additional_clocks` for any one step
+/// below what it already is -- this only ever ties, never loses, against
+/// the fixed-order translation.
+///
+/// There's also no merge step for same-step corrections: `diagonalizing_cliffords`
+/// never emits two corrections on the same qubit within one step, so there's
+/// nothing for a merge pass to combine there either.
+pub fn spc_compact_translation_with_reordering(operators: &[Operator]) -> (Vec<usize>, Vec<CompactStep>) {
+    let width = operators.iter().filter_map(Operator::axis).map(|axis| axis.width()).max().unwrap_or(0);
+    let permutation = xy_colocating_permutation(operators, width);
+    let permuted: Vec<Operator> = operators.iter().map(|op| op.map_axis(&permutation)).collect();
+    (permutation, spc_compact_translation(&permuted))
+}
+
+/// Whether `axis` contains only `Z` and `I` (no `X` or `Y`).
+pub fn has_only_z_and_i(axis: &Axis) -> bool {
+    axis.as_slice().iter().all(|p| matches!(p, Pauli::Z | Pauli::I))
+}
+
+/// Verifies that every step's recorded Clifford corrections actually
+/// reduce its operator's axis to `Z`/`I` only, returning an error
+/// describing the first step for which they don't. Steps whose operator has
+/// no axis (a barrier) have nothing to reduce and are skipped.
+pub fn verify_compact(steps: &[CompactStep]) -> Result<(), String> {
+    for (index, step) in steps.iter().enumerate() {
+        let Some(axis) = step.operator.axis() else {
+            continue;
+        };
+        let mut axis = Rc::new(axis.clone());
+        for clifford in &step.cliffords {
+            axis = transform(axis, clifford);
+        }
+        if !has_only_z_and_i(&axis) {
+            return Err(format!(
+                "compact step {}: axis {} does not reduce to Z/I after applying its recorded cliffords",
+                index, axis
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_spc_compact_translation_fixture() -> Vec<Operator> {
+        // h q[0]; cx q[0],q[1]; t q[1]; measure q[0] -> c[0]; measure q[1] -> c[1];
+        vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::Two),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::X),
+                Angle::PiOver8(Mod8::Two),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 0, Pauli::Z),
+                Angle::PiOver8(Mod8::Two),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_paulis(2, &[(0, Pauli::Z), (1, Pauli::X)]),
+                Angle::PiOver8(Mod8::Six),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(2, 1, Pauli::Z),
+                Angle::PiOver8(Mod8::One),
+            )),
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(2, 0, Pauli::Z),
+                target: 0,
+            },
+            Operator::Measurement {
+                axis: Axis::new_with_pauli(2, 1, Pauli::Z),
+                target: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_spc_compact_translation_verifies() {
+        let steps = spc_compact_translation(&test_spc_compact_translation_fixture());
+        assert!(!steps.is_empty());
+        assert!(verify_compact(&steps).is_ok());
+    }
+
+    #[test]
+    fn test_spc_compact_translation_records_the_sign_diagonalizing_a_measurement_introduces() {
+        // An X measurement on its own (no preceding frame to conjugate it
+        // first) gets diagonalized by a Y correction; X and Y anticommute,
+        // so that correction flips the measurement's sign, which the
+        // compact step must record alongside the correction itself.
+        let ops = vec![Operator::Measurement {
+            axis: Axis::new_with_pauli(1, 0, Pauli::X),
+            target: 0,
+        }];
+
+        let steps = spc_compact_translation(&ops);
+
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].cliffords.len(), 1);
+        assert_eq!(steps[0].sign, Sign::PlusI);
+        assert!(verify_compact(&steps).is_ok());
+    }
+
+    #[test]
+    fn test_verify_compact_fails_on_broken_input() {
+        let mut steps = spc_compact_translation(&test_spc_compact_translation_fixture());
+        // Corrupt the first step by dropping its recorded corrections, so
+        // an X or Y position (if any) survives and verification fails.
+        if let Some(step) = steps.iter_mut().find(|s| !s.cliffords.is_empty()) {
+            step.cliffords.clear();
+            assert!(verify_compact(&steps).is_err());
+        } else {
+            panic!("fixture has no step with corrections to corrupt");
+        }
+    }
+
+    #[test]
+    fn test_has_only_z_and_i() {
+        assert!(has_only_z_and_i(&Axis::new_with_pauli(2, 0, Pauli::Z)));
+        assert!(has_only_z_and_i(&Axis::identity(3)));
+        assert!(!has_only_z_and_i(&Axis::new_with_pauli(2, 0, Pauli::X)));
+    }
+
+    #[test]
+    fn test_additional_clocks_packs_disjoint_corrections_into_one_clock_for_yy() {
+        let axis = Axis::new_with_paulis(2, &[(0, Pauli::Y), (1, Pauli::Y)]);
+        let cliffords = diagonalizing_cliffords(&axis);
+        assert_eq!(cliffords.len(), 2);
+        // A naive one-correction-per-clock count would be 2; since the two
+        // corrections act on disjoint qubits, they pack into a single clock.
+        assert_eq!(additional_clocks(&cliffords), 1);
+    }
+
+    #[test]
+    fn test_ryy_end_to_end_through_spc_compact_translation_packs_into_one_clock() {
+        use crate::frontend::ast::{Argument, GateCall};
+        use crate::frontend::gate::translate_gate;
+        use crate::registers::Registers;
+
+        let mut registers = Registers::new();
+        registers.add_qubit_register("q", 2);
+        let call = GateCall {
+            name: "ryy".to_string(),
+            angles: vec!["pi/4".to_string()],
+            qubits: vec![Argument::Indexed("q".to_string(), 0), Argument::Indexed("q".to_string(), 1)],
+        };
+        let ops = translate_gate(&registers, &call).unwrap();
+
+        let steps = spc_compact_translation(&ops);
+        assert_eq!(steps.len(), 1);
+        // ryy's axis is YY, and its two Y positions diagonalize to
+        // disjoint-qubit corrections that pack into a single clock -- the
+        // same path the direct `..._for_yy` test above checks.
+        assert_eq!(additional_clocks(&steps[0].cliffords), 1);
+        assert!(verify_compact(&steps).is_ok());
+    }
+
+    #[test]
+    fn test_additional_clocks_packs_disjoint_corrections_into_one_clock_for_yyyy() {
+        let axis = Axis::new_with_paulis(
+            4,
+            &[(0, Pauli::Y), (1, Pauli::Y), (2, Pauli::Y), (3, Pauli::Y)],
+        );
+        let cliffords = diagonalizing_cliffords(&axis);
+        assert_eq!(cliffords.len(), 4);
+        assert_eq!(additional_clocks(&cliffords), 1);
+    }
+
+    #[test]
+    fn test_additional_clocks_needs_separate_clocks_for_same_qubit_corrections() {
+        let same_qubit = vec![
+            PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::Y), Angle::PiOver8(Mod8::Two)),
+            PauliRotation::new(Axis::new_with_pauli(1, 0, Pauli::X), Angle::PiOver8(Mod8::Two)),
+        ];
+        assert_eq!(additional_clocks(&same_qubit), 2);
+    }
+
+    #[test]
+    fn test_spc_compact_translation_with_reordering_does_not_increase_total_clocks() {
+        // Scattered X support: qubits 0 and 3 need diagonalizing, 1 and 2
+        // don't, across three separate rotations on a 4-qubit register.
+        let ops = vec![
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(4, 0, Pauli::X),
+                Angle::PiOver8(Mod8::One),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_paulis(4, &[(1, Pauli::Z), (2, Pauli::Z)]),
+                Angle::PiOver8(Mod8::One),
+            )),
+            Operator::PauliRotation(PauliRotation::new(
+                Axis::new_with_pauli(4, 3, Pauli::Y),
+                Angle::PiOver8(Mod8::One),
+            )),
+        ];
+
+        let baseline = spc_compact_translation(&ops);
+        let baseline_clocks: usize = baseline.iter().map(|step| additional_clocks(&step.cliffords)).sum();
+
+        let (permutation, reordered) = spc_compact_translation_with_reordering(&ops);
+        let reordered_clocks: usize = reordered.iter().map(|step| additional_clocks(&step.cliffords)).sum();
+
+        assert!(reordered_clocks <= baseline_clocks);
+        assert!(verify_compact(&reordered).is_ok());
+        // Both X/Y-support qubits (0 and 3) move to the front, in their
+        // original relative order.
+        assert_eq!(permutation[0], 0);
+        assert_eq!(permutation[3], 1);
+    }
+
+    #[test]
+    fn test_additional_clocks_preserves_diagonalization_on_yy_and_yyyy() {
+        // Packing corrections into fewer clocks is just a scheduling count;
+        // it must not change what applying them in order actually does.
+        for axis in [
+            Axis::new_with_paulis(2, &[(0, Pauli::Y), (1, Pauli::Y)]),
+            Axis::new_with_paulis(4, &[(0, Pauli::Y), (1, Pauli::Y), (2, Pauli::Y), (3, Pauli::Y)]),
+        ] {
+            let cliffords = diagonalizing_cliffords(&axis);
+            let mut transformed = Rc::new(axis);
+            for clifford in &cliffords {
+                transformed = transform(transformed, clifford);
+            }
+            assert!(has_only_z_and_i(&transformed));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Deterministic (seeded) random generators for tests and benchmarks. Not
+//! part of the crate's stable API surface, but `pub` so benches/ (which
+//! compiles against `pbc` as an external crate) can reach it.
+
+use crate::angle::Angle;
+use crate::axis::Axis;
+use crate::mod8::Mod8;
+use crate::operator::{Operator, PauliRotation};
+use crate::pauli::Pauli;
+
+/// A small, fast, seedable PRNG (xorshift64*). Not cryptographically
+/// secure; good enough for reproducible test/benchmark fixtures.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // xorshift64* requires a nonzero state.
+        Rng(if seed == 0 { 0xdead_beef_cafe_f00d } else { seed })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+fn random_pauli(rng: &mut Rng) -> Pauli {
+    match rng.below(4) {
+        0 => Pauli::I,
+        1 => Pauli::X,
+        2 => Pauli::Y,
+        _ => Pauli::Z,
+    }
+}
+
+/// A random axis of the given `width`, with each qubit's Pauli drawn
+/// independently and uniformly from `{I, X, Y, Z}`.
+pub fn random_axis(rng: &mut Rng, width: usize) -> Axis {
+    Axis::new((0..width).map(|_| random_pauli(rng)).collect())
+}
+
+fn random_angle(rng: &mut Rng) -> Angle {
+    Angle::PiOver8(Mod8::from(rng.below(8) as u32))
+}
+
+/// A random `PauliRotation` of the given `width`, with a random axis and a
+/// random `pi/8`-aligned angle (so the mix of Clifford and non-Clifford
+/// rotations roughly matches a real circuit).
+pub fn random_pauli_rotation(rng: &mut Rng, width: usize) -> PauliRotation {
+    PauliRotation::new(random_axis(rng, width), random_angle(rng))
+}
+
+/// A random circuit of `depth` operators over `width` qubits: each step is
+/// either a random rotation or a random measurement, picked uniformly.
+/// Deterministic for a given `seed`.
+pub fn random_circuit(seed: u64, width: usize, depth: usize) -> Vec<Operator> {
+    let mut rng = Rng::new(seed);
+    (0..depth)
+        .map(|i| {
+            if rng.below(2) == 0 {
+                Operator::PauliRotation(random_pauli_rotation(&mut rng, width))
+            } else {
+                Operator::Measurement {
+                    axis: random_axis(&mut rng, width),
+                    target: i % width.max(1),
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_is_deterministic_for_a_given_seed() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_random_circuit_is_reproducible_and_sized() {
+        let first = random_circuit(7, 8, 20);
+        let second = random_circuit(7, 8, 20);
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 20);
+    }
+}
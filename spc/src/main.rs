@@ -0,0 +1,1586 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use pbc::spc_compact::{spc_compact_translation, verify_compact, CompactStep};
+use pbc::{
+    extract_with_warnings, logical_frame_sign_changes, longest_anticommuting_chain, parse_pauli_text,
+    parse_with_version, phase_frame, spc_translation, spc_translation_with_frame_trace, t_count_per_qubit, Angle,
+    Axis, Circuit, ExtractOptions, LogicalFrameSign, Operator, PauliRotation, QasmVersion, Registers, Sign,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InputFormat {
+    Qasm,
+    Pauli,
+}
+
+struct Args {
+    filename: Option<String>,
+    files: Option<Vec<String>>,
+    max_width_output: Option<usize>,
+    verify_compact: bool,
+    input_format: InputFormat,
+    save_ir: Option<String>,
+    load_ir: Option<String>,
+    qubit_order: Option<Vec<usize>>,
+    dry_run: bool,
+    trace_frame: bool,
+    stats: bool,
+    reject_arbitrary: bool,
+    summary_json: Option<String>,
+    quiet: bool,
+    qasm_version: QasmVersion,
+    reverse_qubits: bool,
+    operators_only: bool,
+    invert: bool,
+    allow_measurement_invert: bool,
+    magic_only: bool,
+    phase_frame: bool,
+    warn_large_angles: bool,
+}
+
+const USAGE: &str = "usage: spc <file> [--max-width-output N] [--verify-compact] \
+[--input-format qasm|pauli] [--qasm-version 2|3] [--save-ir FILE] [--load-ir FILE] [--qubit-order N,N,...] [--reverse-qubits] [--dry-run] [--trace-frame] [--stats] [--reject-arbitrary] [--summary-json FILE] [--files FILE,FILE,...] [--quiet] [--operators-only] [--invert] [--allow-measurement-invert] [--magic-only] [--phase-frame] [--warn-large-angles]";
+
+fn parse_qubit_order(value: &str) -> Result<Vec<usize>, String> {
+    value
+        .split(',')
+        .map(|token| {
+            token
+                .parse::<usize>()
+                .map_err(|_| format!("invalid --qubit-order value: '{}'", value))
+        })
+        .collect()
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut filename = None;
+    let mut files = None;
+    let mut max_width_output = None;
+    let mut verify_compact = false;
+    let mut input_format = InputFormat::Qasm;
+    let mut save_ir = None;
+    let mut load_ir = None;
+    let mut qubit_order = None;
+    let mut dry_run = false;
+    let mut trace_frame = false;
+    let mut stats = false;
+    let mut reject_arbitrary = false;
+    let mut summary_json = None;
+    let mut quiet = false;
+    let mut qasm_version = QasmVersion::V2;
+    let mut reverse_qubits = false;
+    let mut operators_only = false;
+    let mut invert = false;
+    let mut allow_measurement_invert = false;
+    let mut magic_only = false;
+    let mut phase_frame = false;
+    let mut warn_large_angles = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--max-width-output" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or("--max-width-output requires a value")?;
+                max_width_output = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid --max-width-output value: {}", value))?,
+                );
+            }
+            "--verify-compact" => verify_compact = true,
+            "--input-format" => {
+                i += 1;
+                let value = args.get(i).ok_or("--input-format requires a value")?;
+                input_format = match value.as_str() {
+                    "qasm" => InputFormat::Qasm,
+                    "pauli" => InputFormat::Pauli,
+                    other => return Err(format!("unknown --input-format value: '{}'", other)),
+                };
+            }
+            "--save-ir" => {
+                i += 1;
+                save_ir = Some(args.get(i).ok_or("--save-ir requires a value")?.clone());
+            }
+            "--load-ir" => {
+                i += 1;
+                load_ir = Some(args.get(i).ok_or("--load-ir requires a value")?.clone());
+            }
+            "--qubit-order" => {
+                i += 1;
+                let value = args.get(i).ok_or("--qubit-order requires a value")?;
+                qubit_order = Some(parse_qubit_order(value)?);
+            }
+            "--reverse-qubits" => reverse_qubits = true,
+            "--dry-run" => dry_run = true,
+            "--trace-frame" => trace_frame = true,
+            "--stats" => stats = true,
+            "--reject-arbitrary" => reject_arbitrary = true,
+            "--summary-json" => {
+                i += 1;
+                summary_json = Some(args.get(i).ok_or("--summary-json requires a value")?.clone());
+            }
+            "--files" => {
+                i += 1;
+                let value = args.get(i).ok_or("--files requires a value")?;
+                files = Some(value.split(',').map(String::from).collect());
+            }
+            "--quiet" => quiet = true,
+            "--operators-only" => operators_only = true,
+            "--invert" => invert = true,
+            "--allow-measurement-invert" => allow_measurement_invert = true,
+            "--magic-only" => magic_only = true,
+            "--phase-frame" => phase_frame = true,
+            "--warn-large-angles" => warn_large_angles = true,
+            "--qasm-version" => {
+                i += 1;
+                let value = args.get(i).ok_or("--qasm-version requires a value")?;
+                qasm_version = match value.as_str() {
+                    "2" => QasmVersion::V2,
+                    "3" => QasmVersion::V3,
+                    other => return Err(format!("unknown --qasm-version value: '{}'", other)),
+                };
+            }
+            other => {
+                if filename.is_some() {
+                    return Err(format!("unexpected argument: {}", other));
+                }
+                filename = Some(other.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    if filename.is_some() && files.is_some() {
+        return Err("cannot give both a file and --files".to_string());
+    }
+
+    if filename.is_none() && files.is_none() && load_ir.is_none() {
+        return Err(USAGE.to_string());
+    }
+
+    Ok(Args {
+        filename,
+        files,
+        max_width_output,
+        verify_compact,
+        input_format,
+        save_ir,
+        load_ir,
+        qubit_order,
+        dry_run,
+        trace_frame,
+        stats,
+        reject_arbitrary,
+        summary_json,
+        quiet,
+        qasm_version,
+        reverse_qubits,
+        operators_only,
+        invert,
+        allow_measurement_invert,
+        magic_only,
+        phase_frame,
+        warn_large_angles,
+    })
+}
+
+/// Validates that `permutation` is a bijection on `0..num_qubits`, as
+/// required by `Operator::map_axis`.
+fn validate_permutation(permutation: &[usize], num_qubits: usize) -> Result<(), String> {
+    if permutation.len() != num_qubits {
+        return Err(format!(
+            "--qubit-order has {} entries but the circuit has {} qubits",
+            permutation.len(),
+            num_qubits
+        ));
+    }
+    let mut seen = vec![false; num_qubits];
+    for &qubit in permutation {
+        match seen.get_mut(qubit) {
+            Some(slot) if !*slot => *slot = true,
+            Some(_) => return Err(format!("--qubit-order repeats qubit {}", qubit)),
+            None => {
+                return Err(format!(
+                    "--qubit-order entry {} is out of range for {} qubits",
+                    qubit, num_qubits
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn format_angle(angle: Angle) -> String {
+    match angle {
+        Angle::PiOver8(n) => format!("{}pi/8", n.to_u32()),
+        Angle::Arbitrary(value) => format!("{}", 2.0 * value),
+    }
+}
+
+// Every rendering function below writes into a generic `impl Write` rather
+// than printing directly, so it can be driven against an in-memory buffer
+// in tests (see the `rendering` module) as easily as against stdout. Only
+// the plain-text format exists today; JSON/CSV/HTML output, grouping,
+// headers, and palettes are not implemented, but any that get added later
+// should follow the same pattern and get their own snapshot test.
+
+fn render_axis(axis: &Axis, max_width_output: Option<usize>) -> String {
+    match max_width_output {
+        Some(max_width) => axis.to_truncated_string(max_width),
+        None => axis.to_string(),
+    }
+}
+
+fn render_operator(
+    out: &mut impl Write,
+    op: &Operator,
+    max_width_output: Option<usize>,
+) -> io::Result<()> {
+    write_operator_body(out, op, max_width_output)?;
+    writeln!(out)
+}
+
+/// Writes one operator's text, without a trailing newline, so a
+/// `Conditional` can prefix its `if (...)` guard onto whatever it wraps.
+fn write_operator_body(out: &mut impl Write, op: &Operator, max_width_output: Option<usize>) -> io::Result<()> {
+    match op {
+        Operator::PauliRotation(rotation) => {
+            let axis_str = render_axis(rotation.axis.as_ref(), max_width_output);
+            write!(out, "rotation {} {}", axis_str, format_angle(rotation.angle))
+        }
+        Operator::Measurement { axis, target } => {
+            let axis_str = render_axis(axis, max_width_output);
+            write!(out, "measure {} -> c[{}]", axis_str, target)
+        }
+        Operator::Reset { qubit } => write!(out, "reset q[{}]", qubit),
+        Operator::Barrier(qubits) => {
+            let qubits_str = qubits.iter().map(|q| format!("q[{}]", q)).collect::<Vec<_>>().join(",");
+            write!(out, "barrier {}", qubits_str)
+        }
+        Operator::Conditional { cbits, value, inner } => {
+            let cbits_str = cbits.iter().map(|c| format!("c[{}]", c)).collect::<Vec<_>>().join(",");
+            write!(out, "if ({}=={}) ", cbits_str, value)?;
+            write_operator_body(out, inner, max_width_output)
+        }
+    }
+}
+
+fn render_translation(
+    out: &mut impl Write,
+    operators: &[Operator],
+    max_width_output: Option<usize>,
+) -> io::Result<()> {
+    for op in operators {
+        render_operator(out, op, max_width_output)?;
+    }
+    Ok(())
+}
+
+fn render_compact_summary(out: &mut impl Write, steps: &[CompactStep]) -> io::Result<()> {
+    writeln!(out, "compact translation verified: {} step(s) reduce to Z/I", steps.len())
+}
+
+fn format_sign(sign: Sign) -> &'static str {
+    match sign {
+        Sign::Plus => "+1",
+        Sign::PlusI => "+i",
+        Sign::Minus => "-1",
+        Sign::MinusI => "-i",
+    }
+}
+
+/// Renders the logical-operator frame section: for every qubit whose X or Z
+/// operator picked up a sign from the circuit's absorbed Clifford frame (see
+/// `logical_frame_sign_changes`), one line per changed operator. Qubits with
+/// no sign change are omitted entirely, so the section is empty (but still
+/// headed) for a circuit with no net frame.
+fn render_logical_frame_map(out: &mut impl Write, changes: &[LogicalFrameSign]) -> io::Result<()> {
+    writeln!(out, "logical operator frame:")?;
+    for change in changes {
+        if change.x_sign != Sign::Plus {
+            writeln!(out, "  X[{}] => {}", change.qubit, format_sign(change.x_sign))?;
+        }
+        if change.z_sign != Sign::Plus {
+            writeln!(out, "  Z[{}] => {}", change.qubit, format_sign(change.z_sign))?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the logical operator frame section should render, given `args`.
+/// `--quiet` already suppresses everything but the bare operator lines, and
+/// `--operators-only` exists specifically to drop this section while still
+/// printing the operator listing, so either one suppresses it.
+fn should_render_logical_frame_map(args: &Args) -> bool {
+    !args.quiet && !args.operators_only
+}
+
+/// Renders the `--stats` report: the T-count attributed to each qubit of
+/// the translated circuit, for resource estimation.
+fn render_stats(out: &mut impl Write, translated: &[Operator]) -> io::Result<()> {
+    let counts = t_count_per_qubit(translated);
+    writeln!(out, "T-count per qubit:")?;
+    for (qubit, count) in counts.iter().enumerate() {
+        writeln!(out, "  q[{}]: {}", qubit, count)?;
+    }
+    writeln!(out, "total: {}", counts.iter().sum::<usize>())
+}
+
+/// Renders the step-by-step table produced by `--trace-frame`: one step per
+/// Clifford rotation absorbed into the frame during `spc_translation`,
+/// showing the full frame (every rotation absorbed so far, in order) as of
+/// that step.
+fn render_frame_trace(
+    out: &mut impl Write,
+    trace: &[Vec<PauliRotation>],
+    max_width_output: Option<usize>,
+) -> io::Result<()> {
+    for (step, frame) in trace.iter().enumerate() {
+        writeln!(out, "step {}: frame has {} entry(ies)", step + 1, frame.len())?;
+        for entry in frame {
+            let axis_str = render_axis(entry.axis.as_ref(), max_width_output);
+            writeln!(out, "  {} {}", axis_str, format_angle(entry.angle))?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders the `--phase-frame` report: for each measurement in the
+/// translated circuit, the net Pauli correction (already conjugated
+/// through the absorbed Clifford frame) a real-time decoder would need to
+/// apply to that measurement's outcome.
+fn render_phase_frame(out: &mut impl Write, frame: &[(usize, Axis)], max_width_output: Option<usize>) -> io::Result<()> {
+    for (index, axis) in frame {
+        writeln!(out, "measurement {}: {}", index, render_axis(axis, max_width_output))?;
+    }
+    Ok(())
+}
+
+/// The `--summary-json` sidecar: a machine-readable snapshot of the same
+/// statistics `--stats` prints for humans, plus `depth` (the longest
+/// unavoidably-serialized run of operators, see
+/// `longest_anticommuting_chain`). Written independently of whatever
+/// human-readable format (`--stats` or the plain operator listing) was
+/// also requested, so CI can consume it without parsing text output.
+#[derive(serde::Serialize)]
+struct Summary {
+    operator_count: usize,
+    t_count: usize,
+    t_count_per_qubit: Vec<usize>,
+    depth: usize,
+}
+
+fn build_summary(translated: &[Operator]) -> Summary {
+    let t_count_per_qubit = t_count_per_qubit(translated);
+    let t_count = t_count_per_qubit.iter().sum();
+    Summary {
+        operator_count: translated.len(),
+        t_count,
+        t_count_per_qubit,
+        depth: longest_anticommuting_chain(translated),
+    }
+}
+
+fn write_summary_json(path: &str, translated: &[Operator]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&build_summary(translated)).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("failed to write summary JSON to {}: {}", path, e))
+}
+
+/// Reads and translates `filename` into a `Circuit`. `reject_arbitrary`,
+/// `qasm_version`, and `warn_large_angles` only apply to QASM input: the
+/// Pauli text format has no gates to reject, no `qreg`/`qubit`-style
+/// declarations to version, and no angle literals to flag, since it's
+/// specified directly in terms of axes and angles. Any `--warn-large-angles`
+/// warnings are printed to stderr as they're found.
+fn read_circuit(
+    filename: &str,
+    input_format: InputFormat,
+    reject_arbitrary: bool,
+    qasm_version: QasmVersion,
+    warn_large_angles: bool,
+) -> Result<Circuit, String> {
+    let source =
+        fs::read_to_string(filename).map_err(|e| format!("failed to read {}: {}", filename, e))?;
+    match input_format {
+        InputFormat::Qasm => {
+            let nodes = parse_with_version(&source, qasm_version)?;
+            let options = ExtractOptions { reject_arbitrary, warn_large_angles };
+            let (circuit, warnings) = extract_with_warnings(&nodes, &options)?;
+            for warning in &warnings {
+                eprintln!("warning: {}", warning);
+            }
+            Ok(circuit)
+        }
+        InputFormat::Pauli => parse_pauli_text(&source),
+    }
+}
+
+/// Reindexes `op` (parsed against a circuit with its own, independent
+/// register layout) onto a merged layout: `qubit_map`/`cbit_map` give that
+/// circuit's own flat qubit/classical-bit indices' new positions (see
+/// `Registers::merge`), and `width` is the merged circuit's total qubit
+/// count.
+fn remap_operator(op: Operator, qubit_map: &[usize], cbit_map: &[usize], width: usize) -> Result<Operator, String> {
+    let old_to_new: Vec<Option<usize>> = qubit_map.iter().map(|&i| Some(i)).collect();
+    let relabeled = op.relabel(&old_to_new, width)?;
+    Ok(match relabeled {
+        Operator::Measurement { axis, target } => {
+            let new_target = *cbit_map
+                .get(target)
+                .ok_or_else(|| format!("measurement target c[{}] is out of range", target))?;
+            Operator::Measurement { axis, target: new_target }
+        }
+        other => other,
+    })
+}
+
+/// Reads and translates `filenames` as one concatenated circuit, for
+/// circuits split across modular files: each file's registers are folded
+/// into one merged `Registers` via `Registers::merge` (erroring on a
+/// same-named register with a conflicting size), its operators are
+/// reindexed onto that merged layout, and the per-file operator lists are
+/// appended in file order.
+fn read_concatenated_circuit(
+    filenames: &[String],
+    input_format: InputFormat,
+    reject_arbitrary: bool,
+    qasm_version: QasmVersion,
+    warn_large_angles: bool,
+) -> Result<Circuit, String> {
+    let mut registers = Registers::new();
+    let mut per_file = Vec::new();
+    for filename in filenames {
+        let circuit = read_circuit(filename, input_format, reject_arbitrary, qasm_version, warn_large_angles)?;
+        let (qubit_map, cbit_map) = registers
+            .merge(&circuit.registers)
+            .map_err(|e| format!("{}: {}", filename, e))?;
+        per_file.push((qubit_map, cbit_map, circuit.operators));
+    }
+
+    let width = registers.num_qubits();
+    let mut operators = Vec::new();
+    for (qubit_map, cbit_map, ops) in per_file {
+        for op in ops {
+            operators.push(remap_operator(op, &qubit_map, &cbit_map, width)?);
+        }
+    }
+
+    Ok(Circuit::new(registers, operators))
+}
+
+/// Loads the circuit to translate: from a saved IR checkpoint if
+/// `--load-ir` was given (skipping the QASM/Pauli frontend entirely), from
+/// `args.files` concatenated via `read_concatenated_circuit` if given, or
+/// otherwise from `args.filename` via `read_circuit`. If `--save-ir` was
+/// given, the resulting circuit (post gate-extraction, pre-translation) is
+/// checkpointed to that file before returning -- unless `args.dry_run` is
+/// set, in which case nothing is written.
+fn load_circuit(args: &Args) -> Result<Circuit, String> {
+    let circuit = match (&args.load_ir, &args.files) {
+        (Some(path), _) => {
+            let bytes =
+                fs::read(path).map_err(|e| format!("failed to read IR file {}: {}", path, e))?;
+            pbc::ir::load(&bytes)?
+        }
+        (None, Some(files)) => read_concatenated_circuit(
+            files,
+            args.input_format,
+            args.reject_arbitrary,
+            args.qasm_version,
+            args.warn_large_angles,
+        )?,
+        (None, None) => {
+            let filename = args.filename.as_deref().ok_or(USAGE)?;
+            read_circuit(
+                filename,
+                args.input_format,
+                args.reject_arbitrary,
+                args.qasm_version,
+                args.warn_large_angles,
+            )?
+        }
+    };
+
+    if !args.dry_run {
+        if let Some(path) = &args.save_ir {
+            let bytes = pbc::ir::save(&circuit)?;
+            fs::write(path, bytes).map_err(|e| format!("failed to write IR file {}: {}", path, e))?;
+        }
+    }
+
+    Ok(circuit)
+}
+
+/// Describes where `args` would read the circuit from, for `--dry-run`.
+fn describe_source(args: &Args) -> String {
+    match (&args.load_ir, &args.files, &args.filename) {
+        (Some(path), _, _) => format!("IR checkpoint '{}'", path),
+        (None, Some(files), _) => format!("{} file(s) ({:?} format, concatenated)", files.len(), args.input_format),
+        (None, None, Some(filename)) => format!("'{}' ({:?} format)", filename, args.input_format),
+        (None, None, None) => unreachable!("parse_args requires a filename, --files, or --load-ir"),
+    }
+}
+
+/// Describes what `args` would write and where, for `--dry-run`.
+fn describe_destination(args: &Args) -> String {
+    let mut destination = if args.verify_compact {
+        "compact verification summary to stdout".to_string()
+    } else {
+        "translated operator listing to stdout".to_string()
+    };
+    if let Some(path) = &args.save_ir {
+        destination.push_str(&format!("; circuit checkpoint to '{}'", path));
+    }
+    destination
+}
+
+/// Prints the stages `run` would execute for `args`, plus the register
+/// layout inferred from parsing `circuit`'s source, without running
+/// `spc_translation` or `spc_compact_translation`.
+fn render_dry_run(out: &mut impl Write, args: &Args, circuit: &Circuit) -> io::Result<()> {
+    writeln!(out, "dry run: would execute the following stages")?;
+    writeln!(out, "  1. parse: {}", describe_source(args))?;
+    writeln!(out, "  2. translate: spc_translation")?;
+    if args.verify_compact {
+        writeln!(out, "  3. compact: spc_compact_translation + verify_compact")?;
+        writeln!(out, "  4. output: {}", describe_destination(args))?;
+    } else {
+        writeln!(out, "  3. output: {}", describe_destination(args))?;
+    }
+    writeln!(
+        out,
+        "inferred register layout: {} qubit(s), {} classical bit(s)",
+        circuit.registers.num_qubits(),
+        circuit.registers.num_cbits()
+    )
+}
+
+/// Applies `--qubit-order` (a hardware remapping) to every operator in
+/// `operators`, if one was given.
+fn apply_qubit_order(
+    operators: Vec<Operator>,
+    qubit_order: Option<&[usize]>,
+    num_qubits: usize,
+) -> Result<Vec<Operator>, String> {
+    match qubit_order {
+        None => Ok(operators),
+        Some(permutation) => {
+            validate_permutation(permutation, num_qubits)?;
+            Ok(operators.iter().map(|op| op.map_axis(permutation)).collect())
+        }
+    }
+}
+
+/// Applies `--reverse-qubits` to every operator in `operators`: reverses
+/// each rotation's and measurement's axis (see `Axis::reversed`), flips
+/// each reset's and barrier's qubit index(es), to convert between tools
+/// that index qubits big- vs little-endian. Classical-bit indices (and so a
+/// `Conditional`'s `cbits`) are untouched, since endianness is purely a
+/// qubit-ordering convention.
+fn reverse_qubits(operators: Vec<Operator>, num_qubits: usize) -> Vec<Operator> {
+    operators.into_iter().map(|op| reverse_qubits_op(op, num_qubits)).collect()
+}
+
+fn reverse_qubits_op(op: Operator, num_qubits: usize) -> Operator {
+    match op {
+        Operator::PauliRotation(r) => Operator::PauliRotation(PauliRotation::new(r.axis.reversed(), r.angle)),
+        Operator::Measurement { axis, target } => Operator::Measurement { axis: axis.reversed(), target },
+        Operator::Reset { qubit } => Operator::Reset { qubit: num_qubits - 1 - qubit },
+        Operator::Barrier(qubits) => {
+            Operator::Barrier(qubits.into_iter().map(|q| num_qubits - 1 - q).collect())
+        }
+        Operator::Conditional { cbits, value, inner } => Operator::Conditional {
+            cbits,
+            value,
+            inner: Box::new(reverse_qubits_op(*inner, num_qubits)),
+        },
+    }
+}
+
+/// Applies `--invert`: reverses `operators`' order and daggers each one
+/// (see `Operator::dagger`), producing the inverse circuit -- useful for
+/// debugging uncomputation, where running a circuit forwards then its
+/// inverse should return every qubit to its starting state. A measurement
+/// in the middle makes that undefined (it can't be undone, and reversing
+/// around it changes what gets measured), so this errors unless the
+/// caller opts in with `allow_measurement_invert`.
+fn invert_circuit(operators: Vec<Operator>, allow_measurement_invert: bool) -> Result<Vec<Operator>, String> {
+    if !allow_measurement_invert && operators.iter().any(Operator::is_measurement) {
+        return Err(
+            "--invert: circuit contains measurement(s); pass --allow-measurement-invert to invert anyway"
+                .to_string(),
+        );
+    }
+    Ok(operators.iter().rev().map(Operator::dagger).collect())
+}
+
+/// Whether `--stats` should actually render, given `args`. `--quiet`
+/// exists to suppress non-operator-listing output when a caller just
+/// wants the bare operator lines (e.g. for diffing), so it overrides
+/// `--stats` rather than requiring the two flags to be used consistently.
+fn should_render_stats(args: &Args) -> bool {
+    args.stats && !args.quiet
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let circuit = load_circuit(&args)?;
+    let mut stdout = io::stdout();
+
+    if args.dry_run {
+        render_dry_run(&mut stdout, &args, &circuit).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    let num_qubits = circuit.registers.num_qubits();
+    let operators = apply_qubit_order(circuit.operators, args.qubit_order.as_deref(), num_qubits)?;
+    let operators = if args.reverse_qubits {
+        reverse_qubits(operators, num_qubits)
+    } else {
+        operators
+    };
+    let operators = if args.invert {
+        invert_circuit(operators, args.allow_measurement_invert)?
+    } else {
+        operators
+    };
+
+    if args.verify_compact {
+        let steps = spc_compact_translation(&operators);
+        verify_compact(&steps)?;
+        render_compact_summary(&mut stdout, &steps).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    if args.trace_frame {
+        let (_, trace) = spc_translation_with_frame_trace(&operators);
+        render_frame_trace(&mut stdout, &trace, args.max_width_output).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    let translated = spc_translation(&operators);
+
+    if let Some(path) = &args.summary_json {
+        write_summary_json(path, &translated)?;
+    }
+
+    if args.magic_only {
+        render_translation(&mut stdout, &translated, args.max_width_output).map_err(|e| e.to_string())?;
+        render_stats(&mut stdout, &translated).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    if args.phase_frame {
+        let frame = phase_frame(&translated);
+        render_phase_frame(&mut stdout, &frame, args.max_width_output).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    if should_render_stats(&args) {
+        render_stats(&mut stdout, &translated).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    render_translation(&mut stdout, &translated, args.max_width_output).map_err(|e| e.to_string())?;
+    if should_render_logical_frame_map(&args) {
+        let changes = logical_frame_sign_changes(&operators);
+        render_logical_frame_map(&mut stdout, &changes).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+struct GenArgs {
+    qubits: usize,
+    depth: usize,
+    seed: u64,
+    t_fraction: f64,
+    emit_qasm: String,
+}
+
+const GEN_USAGE: &str =
+    "usage: spc gen --qubits N --depth D --seed S --emit-qasm FILE [--t-fraction F]";
+
+fn parse_gen_args(args: &[String]) -> Result<GenArgs, String> {
+    let mut qubits = None;
+    let mut depth = None;
+    let mut seed = None;
+    let mut t_fraction = 0.25;
+    let mut emit_qasm = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--qubits" => {
+                i += 1;
+                let value = args.get(i).ok_or("--qubits requires a value")?;
+                qubits = Some(
+                    value.parse::<usize>().map_err(|_| format!("invalid --qubits value: {}", value))?,
+                );
+            }
+            "--depth" => {
+                i += 1;
+                let value = args.get(i).ok_or("--depth requires a value")?;
+                depth = Some(
+                    value.parse::<usize>().map_err(|_| format!("invalid --depth value: {}", value))?,
+                );
+            }
+            "--seed" => {
+                i += 1;
+                let value = args.get(i).ok_or("--seed requires a value")?;
+                seed =
+                    Some(value.parse::<u64>().map_err(|_| format!("invalid --seed value: {}", value))?);
+            }
+            "--t-fraction" => {
+                i += 1;
+                let value = args.get(i).ok_or("--t-fraction requires a value")?;
+                t_fraction = value
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid --t-fraction value: {}", value))?;
+            }
+            "--emit-qasm" => {
+                i += 1;
+                emit_qasm = Some(args.get(i).ok_or("--emit-qasm requires a value")?.clone());
+            }
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+        i += 1;
+    }
+
+    Ok(GenArgs {
+        qubits: qubits.ok_or(GEN_USAGE)?,
+        depth: depth.ok_or(GEN_USAGE)?,
+        seed: seed.ok_or(GEN_USAGE)?,
+        t_fraction,
+        emit_qasm: emit_qasm.ok_or(GEN_USAGE)?,
+    })
+}
+
+/// `spc gen`: writes a random Clifford+T circuit to a QASM file instead of
+/// translating one, for benchmarking and fuzzing `spc_translation` without
+/// needing a hand-written input file. This CLI has no `bench` subcommand to
+/// feed the generated file into directly; for now, pipe the emitted file
+/// into a plain `spc <file>` invocation.
+fn run_gen(args: GenArgs) -> Result<(), String> {
+    let qasm = pbc::generate::random_clifford_t_qasm(args.qubits, args.depth, args.t_fraction, args.seed);
+    fs::write(&args.emit_qasm, qasm)
+        .map_err(|e| format!("failed to write {}: {}", args.emit_qasm, e))
+}
+
+fn exit_code(result: Result<(), String>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+    if raw_args.first().map(String::as_str) == Some("gen") {
+        return exit_code(parse_gen_args(&raw_args[1..]).and_then(run_gen));
+    }
+
+    exit_code(parse_args(&raw_args).and_then(run))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert_eq!(args.filename, Some("circuit.qasm".to_string()));
+        assert_eq!(args.max_width_output, None);
+
+        let args = parse_args(&[
+            "circuit.qasm".to_string(),
+            "--max-width-output".to_string(),
+            "20".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.filename, Some("circuit.qasm".to_string()));
+        assert_eq!(args.max_width_output, Some(20));
+    }
+
+    #[test]
+    fn test_parse_args_input_format() {
+        let args = parse_args(&["circuit.ops".to_string()]).unwrap();
+        assert_eq!(args.input_format, InputFormat::Qasm);
+
+        let args = parse_args(&[
+            "circuit.ops".to_string(),
+            "--input-format".to_string(),
+            "pauli".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.input_format, InputFormat::Pauli);
+
+        assert!(parse_args(&[
+            "circuit.ops".to_string(),
+            "--input-format".to_string(),
+            "yaml".to_string(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_args_qasm_version() {
+        let args = parse_args(&["circuit.ops".to_string()]).unwrap();
+        assert_eq!(args.qasm_version, QasmVersion::V2);
+
+        let args = parse_args(&[
+            "circuit.ops".to_string(),
+            "--qasm-version".to_string(),
+            "3".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.qasm_version, QasmVersion::V3);
+
+        assert!(parse_args(&[
+            "circuit.ops".to_string(),
+            "--qasm-version".to_string(),
+            "4".to_string(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_args_missing_filename() {
+        assert!(parse_args(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_save_and_load_ir() {
+        let args = parse_args(&[
+            "circuit.qasm".to_string(),
+            "--save-ir".to_string(),
+            "checkpoint.ir".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.save_ir, Some("checkpoint.ir".to_string()));
+
+        let args = parse_args(&["--load-ir".to_string(), "checkpoint.ir".to_string()]).unwrap();
+        assert_eq!(args.load_ir, Some("checkpoint.ir".to_string()));
+        assert_eq!(args.filename, None);
+    }
+
+    #[test]
+    fn test_parse_args_qubit_order() {
+        let args = parse_args(&[
+            "circuit.qasm".to_string(),
+            "--qubit-order".to_string(),
+            "1,0,2".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.qubit_order, Some(vec![1, 0, 2]));
+
+        assert!(parse_args(&[
+            "circuit.qasm".to_string(),
+            "--qubit-order".to_string(),
+            "a,b".to_string(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_args_dry_run() {
+        let args = parse_args(&["circuit.qasm".to_string(), "--dry-run".to_string()]).unwrap();
+        assert!(args.dry_run);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.dry_run);
+    }
+
+    #[test]
+    fn test_dry_run_prints_stages_and_layout_without_translating() {
+        let dir = env::temp_dir();
+        let path = dir.join("spc_dry_run_test.ops");
+        fs::write(&path, "R +XZ pi/8\nM ZZ\n").unwrap();
+
+        let args = parse_args(&[
+            path.to_str().unwrap().to_string(),
+            "--input-format".to_string(),
+            "pauli".to_string(),
+            "--dry-run".to_string(),
+        ])
+        .unwrap();
+        let circuit = load_circuit(&args).unwrap();
+        fs::remove_file(&path).ok();
+
+        let mut buf = Vec::new();
+        render_dry_run(&mut buf, &args, &circuit).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.contains("1. parse"));
+        assert!(output.contains("2. translate"));
+        assert!(output.contains("3. output"));
+        assert!(output.contains("2 qubit(s)"));
+        assert!(!output.contains("rotation"));
+        assert!(!output.contains("measure"));
+    }
+
+    #[test]
+    fn test_parse_args_trace_frame() {
+        let args = parse_args(&["circuit.qasm".to_string(), "--trace-frame".to_string()]).unwrap();
+        assert!(args.trace_frame);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.trace_frame);
+    }
+
+    #[test]
+    fn test_render_frame_trace_shows_each_absorbed_clifford() {
+        // x q[0]; z q[0]; t q[0];
+        let ops = vec![
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new_with_pauli(1, 0, pbc::Pauli::X),
+                Angle::PiOver8(pbc::Mod8::Four),
+            )),
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new_with_pauli(1, 0, pbc::Pauli::Z),
+                Angle::PiOver8(pbc::Mod8::Four),
+            )),
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new_with_pauli(1, 0, pbc::Pauli::Z),
+                Angle::PiOver8(pbc::Mod8::One),
+            )),
+        ];
+        let (_, trace) = spc_translation_with_frame_trace(&ops);
+
+        let mut buf = Vec::new();
+        render_frame_trace(&mut buf, &trace, None).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert_eq!(output.matches("step").count(), 2);
+        assert!(output.contains("step 1: frame has 1 entry(ies)"));
+        assert!(output.contains("step 2: frame has 2 entry(ies)"));
+    }
+
+    #[test]
+    fn test_parse_args_stats() {
+        let args = parse_args(&["circuit.qasm".to_string(), "--stats".to_string()]).unwrap();
+        assert!(args.stats);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.stats);
+    }
+
+    #[test]
+    fn test_render_stats_reports_t_count_per_qubit() {
+        // A ZZ non-Clifford rotation on qubits 0 and 1 contributes 1 to each.
+        let ops = vec![Operator::PauliRotation(pbc::PauliRotation::new(
+            pbc::Axis::new(vec![pbc::Pauli::Z, pbc::Pauli::Z]),
+            Angle::PiOver8(pbc::Mod8::One),
+        ))];
+
+        let mut buf = Vec::new();
+        render_stats(&mut buf, &ops).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.contains("q[0]: 1"));
+        assert!(output.contains("q[1]: 1"));
+        assert!(output.contains("total: 2"));
+    }
+
+    #[test]
+    fn test_parse_args_summary_json() {
+        let args = parse_args(&[
+            "circuit.qasm".to_string(),
+            "--summary-json".to_string(),
+            "summary.json".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.summary_json, Some("summary.json".to_string()));
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert_eq!(args.summary_json, None);
+    }
+
+    #[test]
+    fn test_write_summary_json_reports_operator_and_t_counts() {
+        // One non-Clifford rotation (T-count 1 on each of its 2 qubits) plus
+        // one measurement: 2 operators total.
+        let ops = vec![
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new(vec![pbc::Pauli::Z, pbc::Pauli::Z]),
+                Angle::PiOver8(pbc::Mod8::One),
+            )),
+            Operator::Measurement {
+                axis: pbc::Axis::new(vec![pbc::Pauli::Z, pbc::Pauli::I]),
+                target: 0,
+            },
+        ];
+
+        let dir = env::temp_dir();
+        let path = dir.join("spc_summary_json_test.json");
+        write_summary_json(path.to_str().unwrap(), &ops).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        let summary: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(summary["operator_count"], 2);
+        assert_eq!(summary["t_count"], 2);
+        assert_eq!(summary["t_count_per_qubit"], serde_json::json!([1, 1]));
+    }
+
+    #[test]
+    fn test_parse_args_reject_arbitrary() {
+        let args =
+            parse_args(&["circuit.qasm".to_string(), "--reject-arbitrary".to_string()]).unwrap();
+        assert!(args.reject_arbitrary);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.reject_arbitrary);
+    }
+
+    #[test]
+    fn test_reject_arbitrary_rejects_an_arbitrary_angle_gate_but_not_without_the_flag() {
+        let dir = env::temp_dir();
+        let path = dir.join("spc_reject_arbitrary_test.qasm");
+        fs::write(&path, "qreg q[1];\nrz(-1.25) q[0];\n").unwrap();
+
+        let without_flag = read_circuit(path.to_str().unwrap(), InputFormat::Qasm, false, QasmVersion::V2, false);
+        assert!(without_flag.is_ok());
+
+        let with_flag = read_circuit(path.to_str().unwrap(), InputFormat::Qasm, true, QasmVersion::V2, false);
+        fs::remove_file(&path).ok();
+        let err = with_flag.unwrap_err();
+        assert!(err.contains("rz"), "error should name the offending gate: {}", err);
+    }
+
+    #[test]
+    fn test_parse_args_warn_large_angles() {
+        let args =
+            parse_args(&["circuit.qasm".to_string(), "--warn-large-angles".to_string()]).unwrap();
+        assert!(args.warn_large_angles);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.warn_large_angles);
+    }
+
+    #[test]
+    fn test_warn_large_angles_flags_an_angle_exceeding_a_full_rotation() {
+        let nodes = pbc::parse_with_version("qreg q[1];\nrz(5*pi/2) q[0];", QasmVersion::V2).unwrap();
+        let options = pbc::ExtractOptions { warn_large_angles: true, ..pbc::ExtractOptions::default() };
+        let (circuit, warnings) = extract_with_warnings(&nodes, &options).unwrap();
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("rz"));
+        assert_eq!(
+            circuit.operators,
+            vec![Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new_with_pauli(1, 0, pbc::Pauli::Z),
+                Angle::PiOver8(pbc::Mod8::Two),
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_quiet() {
+        let args = parse_args(&["circuit.qasm".to_string(), "--quiet".to_string()]).unwrap();
+        assert!(args.quiet);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.quiet);
+    }
+
+    #[test]
+    fn test_quiet_suppresses_stats_output_but_keeps_operator_lines() {
+        // A ZZ non-Clifford rotation on qubits 0 and 1, same fixture as
+        // test_render_stats_reports_t_count_per_qubit.
+        let ops = vec![Operator::PauliRotation(pbc::PauliRotation::new(
+            pbc::Axis::new(vec![pbc::Pauli::Z, pbc::Pauli::Z]),
+            Angle::PiOver8(pbc::Mod8::One),
+        ))];
+
+        let with_stats =
+            parse_args(&["circuit.qasm".to_string(), "--stats".to_string()]).unwrap();
+        assert!(should_render_stats(&with_stats));
+
+        let quiet = parse_args(&[
+            "circuit.qasm".to_string(),
+            "--stats".to_string(),
+            "--quiet".to_string(),
+        ])
+        .unwrap();
+        assert!(!should_render_stats(&quiet));
+
+        let mut buf = Vec::new();
+        render_translation(&mut buf, &ops, None).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.contains("rotation"));
+        assert!(!output.contains("total:"));
+        assert!(!output.contains("T-count"));
+    }
+
+    #[test]
+    fn test_parse_args_magic_only() {
+        let args = parse_args(&["circuit.qasm".to_string(), "--magic-only".to_string()]).unwrap();
+        assert!(args.magic_only);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.magic_only);
+    }
+
+    #[test]
+    fn test_magic_only_prints_both_operators_and_stats() {
+        // A ZZ non-Clifford rotation on qubits 0 and 1, same fixture as
+        // test_render_stats_reports_t_count_per_qubit.
+        let ops = vec![Operator::PauliRotation(pbc::PauliRotation::new(
+            pbc::Axis::new(vec![pbc::Pauli::Z, pbc::Pauli::Z]),
+            Angle::PiOver8(pbc::Mod8::One),
+        ))];
+
+        let mut buf = Vec::new();
+        render_translation(&mut buf, &ops, None).unwrap();
+        render_stats(&mut buf, &ops).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.contains("rotation"));
+        assert!(output.contains("T-count per qubit:"));
+        assert!(output.contains("total: 2"));
+    }
+
+    #[test]
+    fn test_parse_args_phase_frame() {
+        let args = parse_args(&["circuit.qasm".to_string(), "--phase-frame".to_string()]).unwrap();
+        assert!(args.phase_frame);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.phase_frame);
+    }
+
+    #[test]
+    fn test_render_phase_frame_reports_each_measurements_corrected_axis() {
+        let frame = vec![(0, pbc::Axis::new(vec![pbc::Pauli::Z]))];
+
+        let mut buf = Vec::new();
+        render_phase_frame(&mut buf, &frame, None).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert_eq!(output, "measurement 0: Z\n");
+    }
+
+    #[test]
+    fn test_parse_args_operators_only() {
+        let args = parse_args(&["circuit.qasm".to_string(), "--operators-only".to_string()]).unwrap();
+        assert!(args.operators_only);
+
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.operators_only);
+    }
+
+    #[test]
+    fn test_operators_only_suppresses_the_logical_frame_map_but_keeps_operator_lines() {
+        // An S gate on qubit 0 flips its logical X sign, so the logical
+        // frame map has something to report when it isn't suppressed.
+        let ops = vec![Operator::PauliRotation(pbc::PauliRotation::new(
+            pbc::Axis::new(vec![pbc::Pauli::Z]),
+            Angle::PiOver8(pbc::Mod8::Two),
+        ))];
+
+        let with_map =
+            parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(should_render_logical_frame_map(&with_map));
+
+        let operators_only = parse_args(&[
+            "circuit.qasm".to_string(),
+            "--operators-only".to_string(),
+        ])
+        .unwrap();
+        assert!(!should_render_logical_frame_map(&operators_only));
+
+        let mut buf = Vec::new();
+        render_translation(&mut buf, &ops, None).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(output.contains("rotation"));
+        assert!(!output.contains("X[0] =>"));
+        assert!(!output.contains("logical operator frame"));
+    }
+
+    #[test]
+    fn test_parse_args_files() {
+        let args = parse_args(&["--files".to_string(), "a.qasm,b.qasm".to_string()]).unwrap();
+        assert_eq!(args.files, Some(vec!["a.qasm".to_string(), "b.qasm".to_string()]));
+        assert_eq!(args.filename, None);
+
+        assert!(parse_args(&[
+            "circuit.qasm".to_string(),
+            "--files".to_string(),
+            "a.qasm,b.qasm".to_string(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_concatenated_circuit_matches_a_single_merged_file() {
+        let dir = env::temp_dir();
+        let path_a = dir.join("spc_concat_test_a.qasm");
+        let path_b = dir.join("spc_concat_test_b.qasm");
+        let path_merged = dir.join("spc_concat_test_merged.qasm");
+
+        fs::write(&path_a, "OPENQASM 2.0;\nqreg q[2];\nh q[0];\ncx q[0],q[1];\n").unwrap();
+        fs::write(&path_b, "OPENQASM 2.0;\nqreg q[2];\nt q[1];\n").unwrap();
+        fs::write(
+            &path_merged,
+            "OPENQASM 2.0;\nqreg q[2];\nh q[0];\ncx q[0],q[1];\nt q[1];\n",
+        )
+        .unwrap();
+
+        let filenames = vec![
+            path_a.to_str().unwrap().to_string(),
+            path_b.to_str().unwrap().to_string(),
+        ];
+        let concatenated = read_concatenated_circuit(&filenames, InputFormat::Qasm, false, QasmVersion::V2, false).unwrap();
+        let merged = read_circuit(path_merged.to_str().unwrap(), InputFormat::Qasm, false, QasmVersion::V2, false).unwrap();
+
+        fs::remove_file(&path_a).ok();
+        fs::remove_file(&path_b).ok();
+        fs::remove_file(&path_merged).ok();
+
+        assert_eq!(concatenated.registers, merged.registers);
+        assert_eq!(concatenated.operators, merged.operators);
+    }
+
+    #[test]
+    fn test_read_concatenated_circuit_rejects_a_conflicting_register_size() {
+        let dir = env::temp_dir();
+        let path_a = dir.join("spc_concat_conflict_a.qasm");
+        let path_b = dir.join("spc_concat_conflict_b.qasm");
+        fs::write(&path_a, "OPENQASM 2.0;\nqreg q[2];\nh q[0];\n").unwrap();
+        fs::write(&path_b, "OPENQASM 2.0;\nqreg q[3];\nh q[0];\n").unwrap();
+
+        let filenames = vec![
+            path_a.to_str().unwrap().to_string(),
+            path_b.to_str().unwrap().to_string(),
+        ];
+        let result = read_concatenated_circuit(&filenames, InputFormat::Qasm, false, QasmVersion::V2, false);
+
+        fs::remove_file(&path_a).ok();
+        fs::remove_file(&path_b).ok();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_permutation() {
+        assert!(validate_permutation(&[1, 0], 2).is_ok());
+        assert!(validate_permutation(&[1, 0], 3).is_err());
+        assert!(validate_permutation(&[0, 0], 2).is_err());
+        assert!(validate_permutation(&[0, 2], 2).is_err());
+    }
+
+    #[test]
+    fn test_apply_qubit_order_reverses_a_two_qubit_circuit() {
+        let operators = vec![
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new(vec![pbc::Pauli::X, pbc::Pauli::I]),
+                Angle::PiOver8(pbc::Mod8::One),
+            )),
+            Operator::Measurement {
+                axis: pbc::Axis::new(vec![pbc::Pauli::I, pbc::Pauli::Z]),
+                target: 0,
+            },
+        ];
+        let remapped = apply_qubit_order(operators, Some(&[1, 0]), 2).unwrap();
+        assert_eq!(
+            remapped,
+            vec![
+                Operator::PauliRotation(pbc::PauliRotation::new(
+                    pbc::Axis::new(vec![pbc::Pauli::I, pbc::Pauli::X]),
+                    Angle::PiOver8(pbc::Mod8::One),
+                )),
+                Operator::Measurement {
+                    axis: pbc::Axis::new(vec![pbc::Pauli::Z, pbc::Pauli::I]),
+                    target: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reverse_qubits_flips_axes_and_reset_qubits() {
+        let operators = vec![
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new(vec![pbc::Pauli::X, pbc::Pauli::I, pbc::Pauli::Z]),
+                Angle::PiOver8(pbc::Mod8::One),
+            )),
+            Operator::Measurement {
+                axis: pbc::Axis::new(vec![pbc::Pauli::I, pbc::Pauli::Y, pbc::Pauli::I]),
+                target: 0,
+            },
+            Operator::Reset { qubit: 0 },
+        ];
+        let reversed = reverse_qubits(operators, 3);
+        assert_eq!(
+            reversed,
+            vec![
+                Operator::PauliRotation(pbc::PauliRotation::new(
+                    pbc::Axis::new(vec![pbc::Pauli::Z, pbc::Pauli::I, pbc::Pauli::X]),
+                    Angle::PiOver8(pbc::Mod8::One),
+                )),
+                Operator::Measurement {
+                    axis: pbc::Axis::new(vec![pbc::Pauli::I, pbc::Pauli::Y, pbc::Pauli::I]),
+                    target: 0,
+                },
+                Operator::Reset { qubit: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_reverse_qubits() {
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.reverse_qubits);
+
+        let args =
+            parse_args(&["circuit.qasm".to_string(), "--reverse-qubits".to_string()]).unwrap();
+        assert!(args.reverse_qubits);
+    }
+
+    #[test]
+    fn test_parse_args_invert() {
+        let args = parse_args(&["circuit.qasm".to_string()]).unwrap();
+        assert!(!args.invert);
+        assert!(!args.allow_measurement_invert);
+
+        let args = parse_args(&[
+            "circuit.qasm".to_string(),
+            "--invert".to_string(),
+            "--allow-measurement-invert".to_string(),
+        ])
+        .unwrap();
+        assert!(args.invert);
+        assert!(args.allow_measurement_invert);
+    }
+
+    #[test]
+    fn test_invert_circuit_reverses_order_and_negates_rotation_angles() {
+        let operators = vec![
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new(vec![pbc::Pauli::Z]),
+                Angle::PiOver8(pbc::Mod8::One),
+            )),
+            Operator::PauliRotation(pbc::PauliRotation::new(
+                pbc::Axis::new(vec![pbc::Pauli::X]),
+                Angle::PiOver8(pbc::Mod8::Two),
+            )),
+        ];
+
+        let inverted = invert_circuit(operators, false).unwrap();
+
+        assert_eq!(
+            inverted,
+            vec![
+                Operator::PauliRotation(pbc::PauliRotation::new(
+                    pbc::Axis::new(vec![pbc::Pauli::X]),
+                    Angle::PiOver8(pbc::Mod8::Six),
+                )),
+                Operator::PauliRotation(pbc::PauliRotation::new(
+                    pbc::Axis::new(vec![pbc::Pauli::Z]),
+                    Angle::PiOver8(pbc::Mod8::Seven),
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invert_circuit_rejects_measurements_unless_allowed() {
+        let operators = vec![Operator::Measurement {
+            axis: pbc::Axis::new(vec![pbc::Pauli::Z]),
+            target: 0,
+        }];
+
+        assert!(invert_circuit(operators.clone(), false).is_err());
+        assert!(invert_circuit(operators, true).is_ok());
+    }
+
+    #[test]
+    fn test_parse_args_verify_compact() {
+        let args =
+            parse_args(&["circuit.qasm".to_string(), "--verify-compact".to_string()]).unwrap();
+        assert!(args.verify_compact);
+    }
+
+    #[test]
+    fn test_read_circuit_pauli_format_skips_qasm_frontend() {
+        let dir = env::temp_dir();
+        let path = dir.join("spc_pauli_format_test.ops");
+        fs::write(&path, "R +XZ pi/8\nM ZZ\n").unwrap();
+
+        let circuit = read_circuit(path.to_str().unwrap(), InputFormat::Pauli, false, QasmVersion::V2, false).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(circuit.registers.num_qubits(), 2);
+        assert_eq!(circuit.operators.len(), 2);
+        match &circuit.operators[0] {
+            Operator::PauliRotation(r) => assert_eq!(r.angle, Angle::PiOver8(pbc::Mod8::One)),
+            other => panic!("expected a rotation, got {:?}", other),
+        }
+        assert!(circuit.operators[1].is_measurement());
+    }
+
+    #[test]
+    fn test_read_circuit_qasm_version_3_recognizes_qubit_and_bit_declarations() {
+        let dir = env::temp_dir();
+        let path = dir.join("spc_qasm3_test.qasm");
+        fs::write(&path, "qubit[2] q;\nbit[2] c;\nh q[0];\nmeasure q[0] -> c[0];\n").unwrap();
+
+        let circuit = read_circuit(path.to_str().unwrap(), InputFormat::Qasm, false, QasmVersion::V3, false).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(circuit.registers.num_qubits(), 2);
+        assert_eq!(circuit.registers.num_cbits(), 2);
+    }
+
+    #[test]
+    fn test_save_ir_then_load_ir_round_trips_and_matches_single_pass() {
+        let dir = env::temp_dir();
+        let source_path = dir.join("spc_ir_round_trip_source.ops");
+        let ir_path = dir.join("spc_ir_round_trip.ir");
+        fs::write(&source_path, "R +XZ pi/8\nM ZZ\n").unwrap();
+
+        let save_args = parse_args(&[
+            source_path.to_str().unwrap().to_string(),
+            "--input-format".to_string(),
+            "pauli".to_string(),
+            "--save-ir".to_string(),
+            ir_path.to_str().unwrap().to_string(),
+        ])
+        .unwrap();
+        let direct_circuit = load_circuit(&save_args).unwrap();
+
+        let load_args =
+            parse_args(&["--load-ir".to_string(), ir_path.to_str().unwrap().to_string()]).unwrap();
+        let loaded_circuit = load_circuit(&load_args).unwrap();
+
+        fs::remove_file(&source_path).ok();
+        fs::remove_file(&ir_path).ok();
+
+        assert_eq!(loaded_circuit, direct_circuit);
+        assert_eq!(
+            spc_compact_translation(&loaded_circuit.operators),
+            spc_compact_translation(&direct_circuit.operators),
+        );
+    }
+
+    #[test]
+    fn test_print_wide_operator_is_truncated() {
+        let mut paulis = vec![pbc::Pauli::I; 100];
+        paulis[40] = pbc::Pauli::X;
+        paulis[41] = pbc::Pauli::Y;
+        paulis[42] = pbc::Pauli::Z;
+        let axis = pbc::Axis::new(paulis);
+        let truncated = axis.to_truncated_string(20);
+        assert!(truncated.contains("XYZ"));
+        assert!(truncated.len() < 100);
+    }
+
+    #[test]
+    fn test_parse_gen_args() {
+        let args = parse_gen_args(&[
+            "--qubits".to_string(),
+            "4".to_string(),
+            "--depth".to_string(),
+            "20".to_string(),
+            "--seed".to_string(),
+            "7".to_string(),
+            "--emit-qasm".to_string(),
+            "out.qasm".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.qubits, 4);
+        assert_eq!(args.depth, 20);
+        assert_eq!(args.seed, 7);
+        assert_eq!(args.t_fraction, 0.25);
+        assert_eq!(args.emit_qasm, "out.qasm");
+    }
+
+    #[test]
+    fn test_parse_gen_args_missing_required_flag() {
+        assert!(parse_gen_args(&["--qubits".to_string(), "4".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_run_gen_writes_a_qasm_file_that_round_trips() {
+        let dir = env::temp_dir();
+        let path = dir.join("spc_gen_test.qasm");
+
+        run_gen(GenArgs {
+            qubits: 3,
+            depth: 30,
+            seed: 42,
+            t_fraction: 0.3,
+            emit_qasm: path.to_str().unwrap().to_string(),
+        })
+        .unwrap();
+
+        let circuit = read_circuit(path.to_str().unwrap(), InputFormat::Qasm, false, QasmVersion::V2, false).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(circuit.registers.num_qubits(), 3);
+        assert_eq!(circuit.operators, pbc::generate::random_clifford_t(3, 30, 0.3, 42));
+    }
+}
+
+/// Snapshot tests for rendered output, run against one canonical fixture
+/// circuit so formatting regressions show up as a single reviewable diff.
+/// Currently only the plain-text format exists; add a snapshot here
+/// alongside any future format (JSON, CSV, HTML, ...) or feature (grouping,
+/// headers, palettes, ...).
+#[cfg(test)]
+mod snapshot_tests {
+    use super::*;
+    use pbc::parse;
+
+    fn canonical_fixture_circuit() -> Vec<Operator> {
+        let source = "\
+OPENQASM 2.0;
+qreg q[2];
+creg c[2];
+h q[0];
+cx q[0],q[1];
+t q[1];
+measure q[0] -> c[0];
+measure q[1] -> c[1];
+";
+        let nodes = parse(source).unwrap();
+        pbc::extract_with_options(&nodes, &ExtractOptions::default()).unwrap().operators
+    }
+
+    fn render_to_string(operators: &[Operator], max_width_output: Option<usize>) -> String {
+        let mut buf = Vec::new();
+        render_translation(&mut buf, operators, max_width_output).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_text_output_snapshot() {
+        let translated = spc_translation(&canonical_fixture_circuit());
+        insta::assert_snapshot!(render_to_string(&translated, None));
+    }
+
+    #[test]
+    fn test_text_output_snapshot_with_max_width() {
+        let translated = spc_translation(&canonical_fixture_circuit());
+        insta::assert_snapshot!(render_to_string(&translated, Some(10)));
+    }
+
+    #[test]
+    fn test_compact_verification_summary_snapshot() {
+        let steps = spc_compact_translation(&canonical_fixture_circuit());
+        let mut buf = Vec::new();
+        render_compact_summary(&mut buf, &steps).unwrap();
+        insta::assert_snapshot!(String::from_utf8(buf).unwrap());
+    }
+}